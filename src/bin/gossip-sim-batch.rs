@@ -0,0 +1,182 @@
+// Runs the farm headless for a number of in-game days as fast as the machine
+// allows, then writes per-pet metrics for comparing tunings:
+//
+//     cargo run --release --bin gossip-sim-batch -- --days 3 --out sim-output
+//
+// writes sim-output/report.json, sim-output/pets.csv and
+// sim-output/relationships.csv. --tuning takes a json file with any of the
+// `FarmTuning` fields, e.g. {"convo_distance": 24.0, "chat_cooldown_seconds": 5.0}
+use bevy::log::{Level, LogPlugin};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use gossip_farm_sim::prelude::*;
+
+use gossip_farm_sim::gameplay::clock::components::GameClock;
+use gossip_farm_sim::gameplay::components::FarmTuning;
+use gossip_farm_sim::gameplay::player::components::{PetRoster, PlayerData};
+use gossip_farm_sim::gameplay::rng::components::SimRng;
+use gossip_farm_sim::gameplay::simulation::report::SimulationReport;
+use gossip_farm_sim::gameplay::simulation::SimulationPlugin;
+use gossip_farm_sim::gameplay::state::components::GameState;
+use gossip_farm_sim::gameplay::tile::components::MapSettings;
+
+const USAGE: &str =
+    "usage: gossip-sim-batch [--seed N] [--roster pets.json] [--map tiled_maps/farm.tmj]
+                        [--tuning tuning.json] [--days N] [--step SECONDS]
+                        [--assets DIR] [--out DIR]";
+
+// wall clock time the map and sprites get to load before the run is given up
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct BatchArgs {
+    seed: Option<u64>,
+    roster: Option<PathBuf>,
+    map: Option<String>,
+    tuning: Option<PathBuf>,
+    days: u32,
+    step: f64,
+    assets: Option<String>,
+    out: PathBuf,
+}
+
+impl BatchArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut batch_args = Self {
+            seed: None,
+            roster: None,
+            map: None,
+            tuning: None,
+            days: 1,
+            step: 1.0 / 60.0,
+            assets: None,
+            out: PathBuf::from("sim-output"),
+        };
+
+        while let Some(flag) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+            match flag.as_str() {
+                "--seed" => batch_args.seed = Some(parse_number(&value()?, "--seed")?),
+                "--roster" => batch_args.roster = Some(value()?.into()),
+                "--map" => batch_args.map = Some(value()?),
+                "--tuning" => batch_args.tuning = Some(value()?.into()),
+                "--days" => batch_args.days = parse_number(&value()?, "--days")?,
+                "--step" => batch_args.step = parse_number(&value()?, "--step")?,
+                "--assets" => batch_args.assets = Some(value()?),
+                "--out" => batch_args.out = value()?.into(),
+                _ => return Err(format!("unknown argument {}", flag)),
+            }
+        }
+
+        if batch_args.days == 0 {
+            return Err("--days must be at least 1".to_string());
+        }
+        if batch_args.step <= 0.0 {
+            return Err("--step must be positive".to_string());
+        }
+        Ok(batch_args)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got {:?}", flag, value))
+}
+
+fn read_roster(path: &PathBuf) -> Result<Vec<PlayerData>, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|error| format!("could not read roster {:?}: {}", path, error))?;
+    serde_json::from_str(&json)
+        .map_err(|error| format!("could not parse roster {:?}: {}", path, error))
+}
+
+fn read_tuning(path: &PathBuf) -> Result<FarmTuning, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|error| format!("could not read tuning {:?}: {}", path, error))?;
+    serde_json::from_str(&json)
+        .map_err(|error| format!("could not parse tuning {:?}: {}", path, error))
+}
+
+fn run(batch_args: BatchArgs) -> Result<(), String> {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        LogPlugin {
+            level: Level::WARN,
+            ..default()
+        },
+    ));
+    if let Some(assets) = batch_args.assets.clone() {
+        app.add_plugins(AssetPlugin {
+            file_path: assets,
+            ..default()
+        });
+    }
+    app.add_plugins(SimulationPlugin {
+        step: Duration::from_secs_f64(batch_args.step),
+    });
+    if let Some(seed) = batch_args.seed {
+        app.insert_resource(SimRng::new(seed));
+    }
+    if let Some(map) = batch_args.map.clone() {
+        app.insert_resource(MapSettings { path: map });
+    }
+    if let Some(tuning) = batch_args.tuning.as_ref() {
+        app.insert_resource(read_tuning(tuning)?);
+    }
+    if let Some(roster) = batch_args.roster.as_ref() {
+        app.insert_resource(PetRoster {
+            pets: read_roster(roster)?,
+        });
+    }
+    app.finish();
+    app.cleanup();
+
+    let load_started = Instant::now();
+    while *app.world.resource::<State<GameState>>().get() != GameState::Playing {
+        if load_started.elapsed() > LOAD_TIMEOUT {
+            return Err("the farm didn't finish loading, check the map and assets".to_string());
+        }
+        app.update();
+    }
+
+    let day_length_seconds = app.world.resource::<GameClock>().day_length_seconds as f64;
+    let ticks = (batch_args.days as f64 * day_length_seconds / batch_args.step).ceil() as u64;
+    let run_started = Instant::now();
+    for _ in 0..ticks {
+        app.update();
+    }
+
+    let report = SimulationReport::collect(&mut app.world, batch_args.days);
+    std::fs::create_dir_all(&batch_args.out)
+        .map_err(|error| format!("could not create {:?}: {}", batch_args.out, error))?;
+    report
+        .write_json(&batch_args.out.join("report.json"))
+        .and_then(|_| report.write_pets_csv(&batch_args.out.join("pets.csv")))
+        .and_then(|_| report.write_relationships_csv(&batch_args.out.join("relationships.csv")))
+        .map_err(|error| error.to_string())?;
+
+    println!(
+        "Simulated {} day(s) for {} pets in {:.1?}, wrote {:?}",
+        batch_args.days,
+        report.pets.len(),
+        run_started.elapsed(),
+        batch_args.out
+    );
+    Ok(())
+}
+
+fn main() {
+    let batch_args = match BatchArgs::parse(std::env::args().skip(1)) {
+        Ok(batch_args) => batch_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(error) = run(batch_args) {
+        eprintln!("gossip-sim-batch: {}", error);
+        std::process::exit(1);
+    }
+}
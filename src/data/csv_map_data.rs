@@ -1,192 +0,0 @@
-use crate::gameplay::tile::components::CSVMapData;
-
-pub fn get_csv_map_data() -> Vec<CSVMapData> {
-    vec![
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_forest_details_v2.csv",
-            spritesheet_resource_name: "ForestDetailsV2Sheet",
-            z_index: 899.,
-            collision_indices: vec![
-                12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 36, 37, 38, 39, 40, 69, 70, 71, 72, 73,
-                74, 75, 76, 77, 78, 79, 80, 81, 82,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_ramps_north.csv",
-            spritesheet_resource_name: "RampsSheet",
-            z_index: 19.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_apple_tree_1.csv",
-            spritesheet_resource_name: "AppleTreeSheet",
-            z_index: 904.,
-            collision_indices: vec![
-                73, 181, 184, 187, 190, 289, 292, 295, 298, 301, 304, 397, 400, 403, 407, 410, 413,
-                416, 419, 421, 424, 427, 430, 505, 508,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_apple_tree_0.csv",
-            spritesheet_resource_name: "AppleTreeSheet",
-            z_index: 904.,
-            collision_indices: vec![
-                73, 181, 184, 187, 190, 289, 292, 295, 298, 301, 304, 397, 400, 403, 407, 410, 413,
-                416, 419, 421, 424, 427, 430, 505, 508,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_regular_tree_2.csv",
-            spritesheet_resource_name: "RegularTreeSheet",
-            z_index: 906.,
-            collision_indices: vec![
-                73, 181, 184, 187, 190, 289, 292, 295, 298, 301, 304, 397, 400, 403, 407, 410, 413,
-                416, 419, 421, 424, 427, 430, 505, 508,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_regular_tree_1.csv",
-            spritesheet_resource_name: "RegularTreeSheet",
-            z_index: 905.,
-            collision_indices: vec![
-                73, 181, 184, 187, 190, 289, 292, 295, 298, 301, 304, 397, 400, 403, 407, 410, 413,
-                416, 419, 421, 424, 427, 430, 505, 508,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_regular_tree_0.csv",
-            spritesheet_resource_name: "RegularTreeSheet",
-            z_index: 904.,
-            collision_indices: vec![
-                73, 181, 184, 187, 190, 289, 292, 295, 298, 301, 304, 397, 400, 403, 407, 410, 413,
-                416, 419, 421, 424, 427, 430, 505, 508,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_wooden_house_chimney.csv",
-            spritesheet_resource_name: "WoodenHouseSheet",
-            z_index: 903.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_wooden_house_roof.csv",
-            spritesheet_resource_name: "WoodenHouseSheet",
-            z_index: 902.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![3, 4, 5, 9, 10, 11, 15, 16, 17, 21, 22, 23, 27, 28, 29],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_wooden_house_base.csv",
-            spritesheet_resource_name: "WoodenHouseSheet",
-            z_index: 899.,
-            collision_indices: vec![1, 6, 7, 8, 12, 14, 18, 19, 20],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_wooden_house_floor.csv",
-            spritesheet_resource_name: "WoodenHouseSheet",
-            z_index: 18.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_water_objects.csv",
-            spritesheet_resource_name: "WaterObjectsSheet",
-            z_index: 17.,
-            collision_indices: (0..=120).collect(),
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_bushes.csv",
-            spritesheet_resource_name: "BushesSheet",
-            z_index: 901.,
-            collision_indices: (0..=120).collect(),
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_fences.csv",
-            spritesheet_resource_name: "FencesSheet",
-            z_index: 16.,
-            collision_indices: (0..=31).collect(),
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_forest_details.csv",
-            spritesheet_resource_name: "ForestDetailsSheet",
-            z_index: 15.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_darker_grass_patch.csv",
-            spritesheet_resource_name: "DarkerGrassPatchSheet",
-            z_index: 14.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_dark_grass_patch.csv",
-            spritesheet_resource_name: "DarkGrassPatchSheet",
-            z_index: 13.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_soil.csv",
-            spritesheet_resource_name: "SoilSpriteSheet",
-            z_index: 12.,
-            collision_indices: vec![],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_second_ground.csv",
-            spritesheet_resource_name: "DarkGrassHillsSpriteSheet",
-            z_index: 11.,
-            collision_indices: vec![
-                1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 14, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28,
-                29, 30, 31, 32, 33, 34, 35, 36, 38, 39, 41, 42, 44, 45, 46, 47, 48, 49, 50, 51, 52,
-                53, 54,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-        CSVMapData {
-            csv_file_path: "assets/tiled_maps/csv/gossip_farm_sim_v2_ground.csv",
-            spritesheet_resource_name: "DarkGrassWaterSpriteSheet",
-            z_index: 10.,
-            collision_indices: vec![
-                1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 12, 14, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27,
-                28, 29, 30, 31, 32, 33, 34, 35, 36, 38, 39, 41, 42, 44, 45, 46, 47, 48, 49, 50, 51,
-                52, 53,
-            ],
-            passive_collision_indices: vec![],
-            is_vertically_inverted: false,
-        },
-    ]
-}
@@ -1,3 +1 @@
-pub mod csv_map_data;
 pub mod player_data;
-pub mod spawn_data;
@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+use crate::gameplay::state::components::GameState;
+
+// relative to `assets/`
+pub const PEACEFUL_VILLAGE_MUSIC_PATH: &str = "audio/music/peaceful_village.ogg";
+pub const NORMAL_SLASH_SFX_PATH: &str = "audio/sound_effects/normal_slash.ogg";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicTrack {
+    PeacefulVillage,
+}
+
+impl MusicTrack {
+    pub const ALL: [MusicTrack; 1] = [MusicTrack::PeacefulVillage];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicTrack::PeacefulVillage => PEACEFUL_VILLAGE_MUSIC_PATH,
+        }
+    }
+
+    // the track looping in each state, the loading screen stays quiet
+    pub fn for_state(game_state: GameState) -> Option<MusicTrack> {
+        match game_state {
+            GameState::Loading => None,
+            GameState::MainMenu
+            | GameState::Playing
+            | GameState::Pause
+            | GameState::MainDialogue
+            | GameState::SideDialogue => Some(MusicTrack::PeacefulVillage),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    NormalSlash,
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 1] = [SoundEffect::NormalSlash];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            SoundEffect::NormalSlash => NORMAL_SLASH_SFX_PATH,
+        }
+    }
+}
+
+// every clip is loaded once at startup and played from these handles
+#[derive(Resource, Debug, Default)]
+pub struct AudioClips {
+    pub music: HashMap<MusicTrack, Handle<AudioSource>>,
+    pub sound_effects: HashMap<SoundEffect, Handle<AudioSource>>,
+}
+
+// the entity looping the music of the current state
+#[derive(Component, Debug)]
+pub struct MusicPlayer {
+    pub track: MusicTrack,
+}
+
+#[derive(Component, Debug)]
+pub struct SoundEffectPlayer;
+
+#[derive(Resource, Debug, Clone)]
+pub struct AudioSettings {
+    // all volumes go from 0.0 to 1.0, music and sfx are scaled by master
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    // sounds closer than this to the camera play at full volume
+    pub full_volume_distance: f32,
+    // sounds further than this from the camera aren't played
+    pub max_audible_distance: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 0.5,
+            sfx_volume: 0.8,
+            full_volume_distance: TILE_SIZE * 4.0,
+            max_audible_distance: TILE_SIZE * 24.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn music(&self) -> f32 {
+        self.master_volume.clamp(0.0, 1.0) * self.music_volume.clamp(0.0, 1.0)
+    }
+
+    pub fn sfx(&self) -> f32 {
+        self.master_volume.clamp(0.0, 1.0) * self.sfx_volume.clamp(0.0, 1.0)
+    }
+
+    // linear falloff between the full volume and max audible distances
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        if distance <= self.full_volume_distance {
+            return 1.0;
+        }
+        if distance >= self.max_audible_distance {
+            return 0.0;
+        }
+        let falloff = self.max_audible_distance - self.full_volume_distance;
+        1.0 - (distance - self.full_volume_distance) / falloff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attenuation_falls_off_linearly_between_the_distances() {
+        let audio_settings = AudioSettings {
+            full_volume_distance: 100.0,
+            max_audible_distance: 300.0,
+            ..default()
+        };
+        assert_eq!(audio_settings.attenuation(0.0), 1.0);
+        assert_eq!(audio_settings.attenuation(100.0), 1.0);
+        assert_eq!(audio_settings.attenuation(150.0), 0.75);
+        assert_eq!(audio_settings.attenuation(200.0), 0.5);
+        assert_eq!(audio_settings.attenuation(300.0), 0.0);
+        assert_eq!(audio_settings.attenuation(10_000.0), 0.0);
+    }
+
+    #[test]
+    fn attenuation_stays_between_zero_and_one() {
+        let audio_settings = AudioSettings::default();
+        let mut distance = 0.0;
+        let mut last = 1.0;
+        while distance < audio_settings.max_audible_distance * 2.0 {
+            let attenuation = audio_settings.attenuation(distance);
+            assert!(
+                (0.0..=1.0).contains(&attenuation),
+                "{} at {}",
+                attenuation,
+                distance
+            );
+            assert!(attenuation <= last, "louder further away at {}", distance);
+            last = attenuation;
+            distance += 1.0;
+        }
+    }
+
+    #[test]
+    fn volumes_are_clamped_and_scaled_by_master() {
+        let audio_settings = AudioSettings {
+            master_volume: 2.0,
+            music_volume: 0.5,
+            sfx_volume: -1.0,
+            ..default()
+        };
+        assert_eq!(audio_settings.music(), 0.5);
+        assert_eq!(audio_settings.sfx(), 0.0);
+    }
+}
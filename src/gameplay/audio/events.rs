@@ -0,0 +1,10 @@
+use crate::prelude::*;
+
+use crate::gameplay::audio::components::SoundEffect;
+
+#[derive(Event, Debug, Clone)]
+pub struct PlaySoundEffectEvent {
+    pub effect: SoundEffect,
+    // world position of the source, None plays at full volume (menus, ui)
+    pub position: Option<Vec3>,
+}
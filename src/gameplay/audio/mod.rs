@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+use super::state::components::GameState;
+
+mod systems;
+use systems::{apply_music_volume, play_sound_effects, play_state_music, preload_audio_clips};
+
+pub mod components;
+use components::{AudioClips, AudioSettings};
+
+pub mod events;
+use events::PlaySoundEffectEvent;
+
+pub struct AudioSystemPlugin;
+
+impl Plugin for AudioSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>()
+            .add_event::<PlaySoundEffectEvent>()
+            .add_systems(PreStartup, preload_audio_clips)
+            .add_systems(
+                Update,
+                (
+                    play_state_music.run_if(state_changed::<GameState>),
+                    apply_music_volume.run_if(resource_changed::<AudioSettings>),
+                    play_sound_effects,
+                )
+                    .run_if(resource_exists::<AudioClips>),
+            );
+    }
+}
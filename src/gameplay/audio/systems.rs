@@ -0,0 +1,223 @@
+use bevy::audio::Volume;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::audio::components::{
+    AudioClips, AudioSettings, MusicPlayer, MusicTrack, SoundEffect, SoundEffectPlayer,
+};
+use crate::gameplay::camera::components::MainCamera;
+use crate::gameplay::state::components::GameState;
+
+// events
+use crate::gameplay::audio::events::PlaySoundEffectEvent;
+
+pub fn preload_audio_clips(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let music = MusicTrack::ALL
+        .iter()
+        .map(|track| (*track, asset_server.load(track.path())))
+        .collect();
+    let sound_effects = SoundEffect::ALL
+        .iter()
+        .map(|effect| (*effect, asset_server.load(effect.path())))
+        .collect();
+
+    commands.insert_resource(AudioClips {
+        music,
+        sound_effects,
+    });
+}
+
+// Generic over the playback trait so it works the same on a real sink and a
+// mock one
+pub fn sync_music_sink(sink: &impl AudioSinkPlayback, volume: f32, paused: bool) {
+    if sink.volume() != volume {
+        sink.set_volume(volume);
+    }
+    if paused && !sink.is_paused() {
+        sink.pause();
+    } else if !paused && sink.is_paused() {
+        sink.play();
+    }
+}
+
+// Swaps the looping track when the state changes, the music is only paused
+// while the game is
+pub fn play_state_music(
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    audio_clips: Res<AudioClips>,
+    audio_settings: Res<AudioSettings>,
+    music_query: Query<(Entity, &MusicPlayer, Option<&AudioSink>)>,
+) {
+    let track = MusicTrack::for_state(*game_state.get());
+    let paused = *game_state.get() == GameState::Pause;
+
+    let mut already_playing = false;
+    for (music_entity, music_player, sink) in music_query.iter() {
+        if Some(music_player.track) != track {
+            commands.entity(music_entity).despawn();
+            continue;
+        }
+        already_playing = true;
+        if let Some(sink) = sink {
+            sync_music_sink(sink, audio_settings.music(), paused);
+        }
+    }
+
+    let Some(track) = track else {
+        return;
+    };
+    if already_playing {
+        return;
+    }
+    let Some(source) = audio_clips.music.get(&track) else {
+        error!("No clip loaded for {:?}", track);
+        return;
+    };
+
+    let mut settings = PlaybackSettings::LOOP.with_volume(Volume::new(audio_settings.music()));
+    if paused {
+        settings = settings.paused();
+    }
+    commands
+        .spawn(AudioBundle {
+            source: source.clone(),
+            settings,
+        })
+        .insert(Name::new(format!("{:?} Music", track)))
+        .insert(MusicPlayer { track });
+}
+
+pub fn apply_music_volume(
+    game_state: Res<State<GameState>>,
+    audio_settings: Res<AudioSettings>,
+    music_query: Query<&AudioSink, With<MusicPlayer>>,
+) {
+    let paused = *game_state.get() == GameState::Pause;
+    for sink in music_query.iter() {
+        sync_music_sink(sink, audio_settings.music(), paused);
+    }
+}
+
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut sound_effect_event_reader: EventReader<PlaySoundEffectEvent>,
+    audio_clips: Res<AudioClips>,
+    audio_settings: Res<AudioSettings>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+) {
+    let listener = camera_query
+        .get_single()
+        .map(|transform| transform.translation.truncate())
+        .ok();
+
+    for event in sound_effect_event_reader.read() {
+        let attenuation = match (event.position, listener) {
+            (Some(position), Some(listener)) => {
+                audio_settings.attenuation(position.truncate().distance(listener))
+            }
+            _ => 1.0,
+        };
+        let volume = audio_settings.sfx() * attenuation;
+        if volume <= 0.0 {
+            continue;
+        }
+
+        let Some(source) = audio_clips.sound_effects.get(&event.effect) else {
+            error!("No clip loaded for {:?}", event.effect);
+            continue;
+        };
+
+        commands
+            .spawn(AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+            })
+            .insert(Name::new(format!("{:?} Sound Effect", event.effect)))
+            .insert(SoundEffectPlayer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    // Stands in for a rodio sink, counting the calls that would reach it
+    #[derive(Default)]
+    struct MockSink {
+        volume: Cell<f32>,
+        paused: Cell<bool>,
+        volume_sets: Cell<u32>,
+        pauses: Cell<u32>,
+        plays: Cell<u32>,
+    }
+
+    impl AudioSinkPlayback for MockSink {
+        fn volume(&self) -> f32 {
+            self.volume.get()
+        }
+        fn set_volume(&self, volume: f32) {
+            self.volume_sets.set(self.volume_sets.get() + 1);
+            self.volume.set(volume);
+        }
+        fn speed(&self) -> f32 {
+            1.0
+        }
+        fn set_speed(&self, _speed: f32) {}
+        fn play(&self) {
+            self.plays.set(self.plays.get() + 1);
+            self.paused.set(false);
+        }
+        fn pause(&self) {
+            self.pauses.set(self.pauses.get() + 1);
+            self.paused.set(true);
+        }
+        fn is_paused(&self) -> bool {
+            self.paused.get()
+        }
+        fn stop(&self) {}
+        fn empty(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn volume_is_only_set_when_it_changed() {
+        let sink = MockSink::default();
+        sync_music_sink(&sink, 0.5, false);
+        assert_eq!((sink.volume(), sink.volume_sets.get()), (0.5, 1));
+
+        sync_music_sink(&sink, 0.5, false);
+        assert_eq!(sink.volume_sets.get(), 1);
+
+        sync_music_sink(&sink, 0.25, false);
+        assert_eq!((sink.volume(), sink.volume_sets.get()), (0.25, 2));
+    }
+
+    #[test]
+    fn sink_pauses_and_resumes_with_the_game() {
+        let sink = MockSink::default();
+        sync_music_sink(&sink, 0.5, true);
+        assert!(sink.is_paused());
+        sync_music_sink(&sink, 0.5, true);
+        assert_eq!(sink.pauses.get(), 1);
+
+        sync_music_sink(&sink, 0.5, false);
+        assert!(!sink.is_paused());
+        sync_music_sink(&sink, 0.5, false);
+        assert_eq!(sink.plays.get(), 1);
+    }
+
+    #[test]
+    fn volume_follows_settings_while_paused() {
+        let sink = MockSink::default();
+        let mut audio_settings = AudioSettings::default();
+        sync_music_sink(&sink, audio_settings.music(), true);
+        audio_settings.master_volume = 0.5;
+        sync_music_sink(&sink, audio_settings.music(), true);
+        assert_eq!(sink.volume(), 0.25);
+        assert!(sink.is_paused());
+    }
+}
@@ -0,0 +1,405 @@
+use rand::Rng;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::{
+    Behaviour, BehaviourAgent, BehaviourContext, BehaviourKind, BehaviourStatus,
+};
+use crate::gameplay::components::{DirectionIntent, GenericActions};
+use crate::gameplay::needs::components::{Need, BED_LANDMARK, FOOD_LANDMARK};
+use crate::gameplay::pathfinding::astar::{find_conversation_path, find_path_to_nearest};
+use crate::gameplay::player::components::BasicEmotions;
+use crate::gameplay::relationship::components::SOCIAL_PULL_WEIGHT;
+use crate::gameplay::tile::collision::entity_footprint;
+
+// what eating and sleeping score while under way, so a pet finishes its
+// meal or nap unless something scary comes along
+const COMMITTED_SCORE: f32 = 0.8;
+// pets head to bed at night however rested they are
+const BEDTIME_SCORE: f32 = 0.9;
+
+enum PathProgress {
+    Walking,
+    Arrived,
+    Stuck,
+}
+
+fn walk_speed(agent: &BehaviourAgent, context: &BehaviourContext) -> f32 {
+    agent.roaming.roam_speed * context.mood.speed_factor() * TILE_SIZE
+}
+
+fn random_seconds(agent: &mut BehaviourAgent, min: f32, max: f32) -> f32 {
+    agent.rng.gen_range(min..=max.max(min))
+}
+
+// Moves the pet by `delta`, sliding along walls. False when it couldn't
+// move at all.
+fn step(agent: &mut BehaviourAgent, context: &BehaviourContext, delta: Vec2) -> bool {
+    let position = agent.transform.translation.truncate();
+    let swept =
+        context
+            .collision_grid
+            .sweep_aabb(position, entity_footprint(agent.collider), delta);
+    if swept == position && delta != Vec2::ZERO {
+        return false;
+    }
+
+    agent.transform.translation.x = swept.x;
+    agent.transform.translation.y = swept.y;
+    agent.action_state.action = GenericActions::Walk;
+    agent.animation.last_direction = DirectionIntent::from_direction(delta);
+    true
+}
+
+fn walk_path(agent: &mut BehaviourAgent, context: &BehaviourContext) -> PathProgress {
+    let Some(&waypoint) = agent.active.path.front() else {
+        return PathProgress::Arrived;
+    };
+
+    let to_waypoint = waypoint - agent.transform.translation.truncate();
+    let distance = walk_speed(agent, context) * agent.delta_seconds;
+    let delta = if to_waypoint.length() <= distance {
+        agent.active.path.pop_front();
+        to_waypoint
+    } else {
+        to_waypoint.normalize() * distance
+    };
+
+    if step(agent, context, delta) {
+        PathProgress::Walking
+    } else {
+        PathProgress::Stuck
+    }
+}
+
+// Walks the planned path and gives up when it takes too long or something
+// moved into the way. Some(status) while the pet isn't there yet.
+fn walk_to_destination(
+    agent: &mut BehaviourAgent,
+    context: &BehaviourContext,
+) -> Option<BehaviourStatus> {
+    if agent.active.arrived {
+        return None;
+    }
+    if agent.active.elapsed > context.settings.give_up_seconds {
+        return Some(BehaviourStatus::Failed);
+    }
+
+    match walk_path(agent, context) {
+        PathProgress::Walking => Some(BehaviourStatus::Running),
+        PathProgress::Stuck => Some(BehaviourStatus::Failed),
+        PathProgress::Arrived => {
+            agent.active.arrived = true;
+            agent.animation.current_frame = 0;
+            None
+        }
+    }
+}
+
+// Wanders around, drifting toward friends and away from rivals
+pub struct RoamBehaviour;
+
+impl Behaviour for RoamBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::Roam
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        // excited pets can't sit still, sad ones mope about
+        0.35 * context.mood.speed_factor()
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        let wander = Vec2::new(
+            agent.rng.gen_range(-1.0..=1.0),
+            agent.rng.gen_range(-1.0..=1.0),
+        );
+        let pet_positions: Vec<(String, Vec2)> = context
+            .others
+            .iter()
+            .map(|other| (other.uuid.clone(), other.position))
+            .collect();
+        let social_pull =
+            context
+                .relationship_graph
+                .social_pull(context.uuid, context.position, &pet_positions);
+
+        agent.active.direction = (wander + social_pull * SOCIAL_PULL_WEIGHT).normalize_or_zero();
+        if agent.active.direction == Vec2::ZERO {
+            return BehaviourStatus::Done;
+        }
+        agent.active.duration = random_seconds(
+            agent,
+            context.settings.min_roam_seconds,
+            agent.roaming.roam_max_time,
+        );
+        BehaviourStatus::Running
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        if agent.active.elapsed > agent.active.duration {
+            return BehaviourStatus::Done;
+        }
+
+        let delta = agent.active.direction * walk_speed(agent, context) * agent.delta_seconds;
+        if step(agent, context, delta) {
+            BehaviourStatus::Running
+        } else {
+            BehaviourStatus::Done
+        }
+    }
+}
+
+// Stands around for a bit, more so when tired
+pub struct IdleBehaviour;
+
+impl Behaviour for IdleBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::Idle
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        0.3 + 0.3 * context.needs.urgency(Need::Energy)
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        agent.active.duration = random_seconds(
+            agent,
+            context.settings.min_idle_seconds,
+            agent.roaming.idle_max_time,
+        );
+        agent.action_state.action = GenericActions::Idle;
+        BehaviourStatus::Running
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, _context: &BehaviourContext) -> BehaviourStatus {
+        agent.action_state.action = GenericActions::Idle;
+        if agent.active.elapsed > agent.active.duration {
+            BehaviourStatus::Done
+        } else {
+            BehaviourStatus::Running
+        }
+    }
+}
+
+// Walks up to the closest pet it gets along with and waits for a chat to
+// start, the proximity detection takes it from there
+pub struct SeekChatBehaviour;
+
+impl Behaviour for SeekChatBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::SeekChat
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        if !context.can_chat || context.nearest_chat_partner().is_none() {
+            return 0.0;
+        }
+        context.needs.urgency(Need::Social)
+            * context.mood.chat_willingness()
+            * context.clock.chattiness()
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        let Some(partner) = context.nearest_chat_partner() else {
+            return BehaviourStatus::Failed;
+        };
+        // stop a little inside chatting range so the chat kicks in
+        match find_conversation_path(
+            context.collision_grid,
+            context.position,
+            partner.position,
+            None,
+            context.tuning.convo_distance * 0.8,
+        ) {
+            Some(conversation_path) => {
+                agent.active.path = conversation_path.waypoints.into();
+                BehaviourStatus::Running
+            }
+            None => BehaviourStatus::Failed,
+        }
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        if let Some(status) = walk_to_destination(agent, context) {
+            return status;
+        }
+
+        // the other pet wandered off
+        agent.action_state.action = GenericActions::Idle;
+        if agent.active.elapsed > context.settings.give_up_seconds {
+            BehaviourStatus::Failed
+        } else {
+            BehaviourStatus::Running
+        }
+    }
+}
+
+// Walks to the closest food landmark and eats until full
+pub struct EatBehaviour;
+
+impl Behaviour for EatBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::Eat
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        let hunger = context.needs.urgency(Need::Hunger);
+        if context.loaded_map.landmarks(FOOD_LANDMARK).is_empty()
+            || hunger <= context.needs_settings.satisfied_threshold
+        {
+            return 0.0;
+        }
+        if context.current == Some(BehaviourKind::Eat) {
+            return COMMITTED_SCORE;
+        }
+        // barely thinks about food until it's properly hungry
+        (hunger * hunger * 1.5).min(1.0)
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        match find_path_to_nearest(
+            context.collision_grid,
+            context.position,
+            context.loaded_map.landmarks(FOOD_LANDMARK),
+            context.settings.max_path_attempts,
+        ) {
+            Some(path) => {
+                agent.active.path = path.into();
+                BehaviourStatus::Running
+            }
+            None => BehaviourStatus::Failed,
+        }
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        if let Some(status) = walk_to_destination(agent, context) {
+            return status;
+        }
+
+        if context.needs.urgency(Need::Hunger) <= context.needs_settings.satisfied_threshold {
+            agent.action_state.action = GenericActions::Idle;
+            return BehaviourStatus::Done;
+        }
+        agent.action_state.action = GenericActions::Eating;
+        BehaviourStatus::Running
+    }
+}
+
+// Runs from pets it can't stand, scared pets run sooner
+pub struct FleeBehaviour;
+
+impl Behaviour for FleeBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::Flee
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        let fear = context.mood.level(BasicEmotions::Fear);
+        context
+            .threats()
+            .map(|(_, closeness)| closeness * (0.5 + fear))
+            .fold(0.0, f32::max)
+            .min(1.0)
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        let away: Vec2 = context
+            .threats()
+            .map(|(threat, closeness)| {
+                (context.position - threat.position).normalize_or_zero() * closeness
+            })
+            .sum();
+
+        agent.active.direction = away.normalize_or_zero();
+        if agent.active.direction == Vec2::ZERO {
+            return BehaviourStatus::Failed;
+        }
+        agent.active.duration = context.settings.flee_seconds;
+        BehaviourStatus::Running
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        if agent.active.elapsed > agent.active.duration {
+            return BehaviourStatus::Done;
+        }
+
+        let delta = agent.active.direction
+            * walk_speed(agent, context)
+            * context.settings.flee_speed_factor
+            * agent.delta_seconds;
+        if step(agent, context, delta) {
+            BehaviourStatus::Running
+        } else {
+            // cornered, think again
+            BehaviourStatus::Done
+        }
+    }
+}
+
+// Walks to bed and sleeps until rested, or rests on the spot when there's
+// no bed in reach
+pub struct SleepBehaviour;
+
+impl Behaviour for SleepBehaviour {
+    fn kind(&self) -> BehaviourKind {
+        BehaviourKind::Sleep
+    }
+
+    fn score(&self, context: &BehaviourContext) -> f32 {
+        if context.clock.is_night() {
+            return BEDTIME_SCORE;
+        }
+        let tiredness = context.needs.urgency(Need::Energy);
+        if tiredness <= context.needs_settings.satisfied_threshold {
+            return 0.0;
+        }
+        if context.current == Some(BehaviourKind::Sleep) {
+            return COMMITTED_SCORE;
+        }
+        (tiredness * tiredness * 1.5).min(1.0)
+    }
+
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        match find_path_to_nearest(
+            context.collision_grid,
+            context.position,
+            context.loaded_map.landmarks(BED_LANDMARK),
+            context.settings.max_path_attempts,
+        ) {
+            Some(path) => agent.active.path = path.into(),
+            None => agent.active.sleeps_rough = true,
+        }
+        BehaviourStatus::Running
+    }
+
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus {
+        if !agent.active.arrived {
+            if let Some(status) = walk_to_destination(agent, context) {
+                return status;
+            }
+            agent.action_state.action = GenericActions::IntoRest;
+            return BehaviourStatus::Running;
+        }
+
+        // sleeps through the night, then until rested
+        let rested = !context.clock.is_night()
+            && context.needs.urgency(Need::Energy) <= context.needs_settings.satisfied_threshold;
+        match agent.action_state.action {
+            // the animation moves on to Resting
+            GenericActions::IntoRest => {}
+            GenericActions::Resting if !agent.active.sleeps_rough && !rested => {
+                agent.action_state.action = GenericActions::Sleeping;
+            }
+            GenericActions::Resting | GenericActions::Sleeping if rested => {
+                agent.animation.current_frame = 0;
+                agent.action_state.action = GenericActions::OutOfRest;
+            }
+            // the animation sets Idle once the pet is back on its feet
+            GenericActions::Idle => return BehaviourStatus::Done,
+            _ => {}
+        }
+        BehaviourStatus::Running
+    }
+}
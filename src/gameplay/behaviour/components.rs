@@ -0,0 +1,348 @@
+use bevy::reflect::TypePath;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+use crate::gameplay::clock::components::GameClock;
+use crate::gameplay::components::{
+    Animation, BasicEntityCollider, EntityActionState, FarmTuning, Roaming,
+};
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::needs::components::{Needs, NeedsSettings};
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::EntityRng;
+use crate::gameplay::tile::collision::CollisionGrid;
+use crate::gameplay::tile::components::LoadedMap;
+
+use super::behaviours::{
+    EatBehaviour, FleeBehaviour, IdleBehaviour, RoamBehaviour, SeekChatBehaviour, SleepBehaviour,
+};
+
+pub const BEHAVIOUR_PROFILES_PATH: &str = "behaviours/profiles.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviourKind {
+    Roam,
+    Idle,
+    SeekChat,
+    Eat,
+    Flee,
+    Sleep,
+}
+
+impl BehaviourKind {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// Multiplies each behaviour's score for one pet, 1.0 leaves it as is and
+// 0.0 switches the behaviour off
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct BehaviourWeights {
+    // indexed in `BehaviourKind` declaration order
+    pub weights: [f32; 6],
+}
+
+impl Default for BehaviourWeights {
+    fn default() -> Self {
+        Self { weights: [1.0; 6] }
+    }
+}
+
+impl BehaviourWeights {
+    pub fn get(&self, kind: BehaviourKind) -> f32 {
+        self.weights[kind.index()]
+    }
+
+    pub fn set(&mut self, kind: BehaviourKind, weight: f32) {
+        self.weights[kind.index()] = weight.max(0.0);
+    }
+
+    pub fn with_overrides(mut self, overrides: &HashMap<BehaviourKind, f32>) -> Self {
+        for (kind, weight) in overrides.iter() {
+            self.set(*kind, *weight);
+        }
+        self
+    }
+}
+
+// Behaviour weights tuned by designers in `BEHAVIOUR_PROFILES_PATH`.
+// `default` applies to every pet, `pets` overrides single behaviours for a
+// pet by name. Anything left out stays at 1.0.
+#[derive(Asset, TypePath, Deserialize, Debug, Default)]
+pub struct BehaviourProfiles {
+    #[serde(default)]
+    pub default: HashMap<BehaviourKind, f32>,
+    #[serde(default)]
+    pub pets: HashMap<String, HashMap<BehaviourKind, f32>>,
+}
+
+impl BehaviourProfiles {
+    pub fn weights_for(&self, pet_name: &str) -> BehaviourWeights {
+        let weights = BehaviourWeights::default().with_overrides(&self.default);
+        match self.pets.get(pet_name) {
+            Some(overrides) => weights.with_overrides(overrides),
+            None => weights,
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct BehaviourProfilesHandle(pub Handle<BehaviourProfiles>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviourStatus {
+    Running,
+    Done,
+    // couldn't happen, e.g. no path, the behaviour cools down before it's
+    // considered again
+    Failed,
+}
+
+// What a pet is doing right now and the state its behaviour keeps
+#[derive(Component, Debug, Clone)]
+pub struct ActiveBehaviour {
+    pub kind: BehaviourKind,
+    pub status: BehaviourStatus,
+    // seconds since the behaviour started
+    pub elapsed: f32,
+    // seconds since the scores were last compared
+    pub since_evaluated: f32,
+    // how long time-boxed behaviours like roaming or idling last
+    pub duration: f32,
+    pub direction: Vec2,
+    pub path: VecDeque<Vec2>,
+    // got where it was going and the behaviour itself has started
+    pub arrived: bool,
+    // tired with no bed in reach, rests on the spot instead of sleeping
+    pub sleeps_rough: bool,
+    // seconds left before a failed behaviour is considered again, indexed in
+    // `BehaviourKind` declaration order
+    pub cooldowns: [f32; 6],
+}
+
+impl Default for ActiveBehaviour {
+    fn default() -> Self {
+        Self {
+            kind: BehaviourKind::Idle,
+            status: BehaviourStatus::Done,
+            elapsed: 0.0,
+            since_evaluated: 0.0,
+            duration: 0.0,
+            direction: Vec2::ZERO,
+            path: VecDeque::new(),
+            arrived: false,
+            sleeps_rough: false,
+            cooldowns: [0.0; 6],
+        }
+    }
+}
+
+impl ActiveBehaviour {
+    // resets the per-behaviour state, cooldowns carry over
+    pub fn begin(&mut self, kind: BehaviourKind) {
+        *self = Self {
+            kind,
+            status: BehaviourStatus::Running,
+            cooldowns: self.cooldowns,
+            ..Default::default()
+        };
+    }
+
+    pub fn cool_down(&mut self, kind: BehaviourKind, seconds: f32) {
+        self.cooldowns[kind.index()] = seconds;
+    }
+
+    pub fn is_cooling_down(&self, kind: BehaviourKind) -> bool {
+        self.cooldowns[kind.index()] > 0.0
+    }
+
+    pub fn tick(&mut self, delta_seconds: f32) {
+        self.elapsed += delta_seconds;
+        self.since_evaluated += delta_seconds;
+        for cooldown in self.cooldowns.iter_mut() {
+            *cooldown = (*cooldown - delta_seconds).max(0.0);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != BehaviourStatus::Running
+    }
+
+    // eating or sleeping, too busy to chat
+    pub fn is_occupied(&self) -> bool {
+        self.arrived
+            && !self.is_finished()
+            && matches!(self.kind, BehaviourKind::Eat | BehaviourKind::Sleep)
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct BehaviourSettings {
+    // how often a busy pet checks whether something else matters more
+    pub reevaluate_seconds: f32,
+    // bonus the running behaviour gets, so two close scores don't flicker
+    pub switch_margin: f32,
+    // scores are scaled by a random factor up to this far from 1.0, so close
+    // calls like roaming or idling don't go the same way every time
+    pub score_noise: f32,
+    pub failed_cooldown_seconds: f32,
+    // abandons walking somewhere that takes longer than this
+    pub give_up_seconds: f32,
+    // how many of the closest landmark tiles to try pathing to
+    pub max_path_attempts: usize,
+    // pets further than this aren't considered for company
+    pub socialize_distance: f32,
+    // disliked pets closer than this are run from
+    pub flee_distance: f32,
+    pub flee_seconds: f32,
+    pub flee_speed_factor: f32,
+    pub min_idle_seconds: f32,
+    pub min_roam_seconds: f32,
+}
+
+impl Default for BehaviourSettings {
+    fn default() -> Self {
+        Self {
+            reevaluate_seconds: 0.5,
+            switch_margin: 0.1,
+            score_noise: 0.25,
+            failed_cooldown_seconds: 8.0,
+            give_up_seconds: 20.0,
+            max_path_attempts: 8,
+            socialize_distance: TILE_SIZE * 20.0,
+            flee_distance: TILE_SIZE * 5.0,
+            flee_seconds: 2.0,
+            flee_speed_factor: 1.5,
+            min_idle_seconds: 1.0,
+            min_roam_seconds: 1.5,
+        }
+    }
+}
+
+// Another pet as seen by the pet deciding what to do
+#[derive(Debug, Clone)]
+pub struct PetSnapshot {
+    pub entity: Entity,
+    pub uuid: String,
+    pub position: Vec2,
+    // roaming, not chatting, eating or sleeping
+    pub available: bool,
+}
+
+// Everything a behaviour can look at while scoring or running
+pub struct BehaviourContext<'a> {
+    pub entity: Entity,
+    pub uuid: &'a str,
+    pub position: Vec2,
+    // the behaviour that's running, None between behaviours
+    pub current: Option<BehaviourKind>,
+    pub needs: &'a Needs,
+    pub mood: &'a Mood,
+    pub can_chat: bool,
+    pub others: &'a [PetSnapshot],
+    pub collision_grid: &'a CollisionGrid,
+    pub loaded_map: &'a LoadedMap,
+    pub relationship_graph: &'a RelationshipGraph,
+    pub needs_settings: &'a NeedsSettings,
+    pub settings: &'a BehaviourSettings,
+    pub clock: &'a GameClock,
+    pub tuning: &'a FarmTuning,
+}
+
+impl BehaviourContext<'_> {
+    // the closest pet free to chat that neither side avoids
+    pub fn nearest_chat_partner(&self) -> Option<&PetSnapshot> {
+        self.others
+            .iter()
+            .filter(|other| {
+                other.entity != self.entity
+                    && other.available
+                    && self.position.distance(other.position) <= self.settings.socialize_distance
+                    && !self.relationship_graph.avoids(self.uuid, &other.uuid)
+                    && !self.relationship_graph.avoids(&other.uuid, self.uuid)
+            })
+            .min_by(|a, b| {
+                self.position
+                    .distance(a.position)
+                    .total_cmp(&self.position.distance(b.position))
+            })
+    }
+
+    // disliked pets within `flee_distance`, paired with how close they are
+    // from 0.0 (at the edge) to 1.0 (right there)
+    pub fn threats(&self) -> impl Iterator<Item = (&PetSnapshot, f32)> {
+        self.others.iter().filter_map(|other| {
+            let distance = self.position.distance(other.position);
+            (other.entity != self.entity
+                && distance < self.settings.flee_distance
+                && self.relationship_graph.avoids(self.uuid, &other.uuid))
+            .then(|| (other, 1.0 - distance / self.settings.flee_distance))
+        })
+    }
+}
+
+// The parts of a pet a behaviour can change
+pub struct BehaviourAgent<'a> {
+    pub active: &'a mut ActiveBehaviour,
+    pub transform: &'a mut Transform,
+    pub animation: &'a mut Animation,
+    pub action_state: &'a mut EntityActionState,
+    pub collider: &'a BasicEntityCollider,
+    pub roaming: &'a Roaming,
+    pub rng: &'a mut EntityRng,
+    pub delta_seconds: f32,
+}
+
+// One thing a pet can decide to do. `score` says how much the pet wants to
+// right now, from 0.0 to 1.0 before the pet's weights, `start` sets up
+// `ActiveBehaviour` when it's picked and `execute` runs every frame after.
+pub trait Behaviour: Send + Sync {
+    fn kind(&self) -> BehaviourKind;
+    fn score(&self, context: &BehaviourContext) -> f32;
+    fn start(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus;
+    fn execute(&self, agent: &mut BehaviourAgent, context: &BehaviourContext) -> BehaviourStatus;
+}
+
+// Every behaviour pets choose from. Plugins can register more with
+// `world.resource_mut::<BehaviourRegistry>().register(...)`, a behaviour
+// registered for a kind that's already taken replaces the old one.
+#[derive(Resource)]
+pub struct BehaviourRegistry {
+    pub behaviours: Vec<Box<dyn Behaviour>>,
+}
+
+impl BehaviourRegistry {
+    pub fn register(&mut self, behaviour: impl Behaviour + 'static) -> &mut Self {
+        self.behaviours
+            .retain(|existing| existing.kind() != behaviour.kind());
+        self.behaviours.push(Box::new(behaviour));
+        self
+    }
+
+    pub fn get(&self, kind: BehaviourKind) -> Option<&dyn Behaviour> {
+        self.behaviours
+            .iter()
+            .find(|behaviour| behaviour.kind() == kind)
+            .map(|behaviour| behaviour.as_ref())
+    }
+}
+
+impl Default for BehaviourRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            behaviours: Vec::new(),
+        };
+        registry
+            .register(RoamBehaviour)
+            .register(IdleBehaviour)
+            .register(SeekChatBehaviour)
+            .register(EatBehaviour)
+            .register(FleeBehaviour)
+            .register(SleepBehaviour);
+        registry
+    }
+}
@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::tile::collision::CollisionGrid;
+
+mod systems;
+use systems::{apply_behaviour_profiles, load_behaviour_profiles, update_behaviours};
+
+pub mod behaviours;
+
+pub mod components;
+use components::{BehaviourProfiles, BehaviourRegistry, BehaviourSettings};
+
+pub mod profiles;
+use profiles::BehaviourProfilesLoader;
+
+pub struct BehaviourPlugin;
+
+impl Plugin for BehaviourPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BehaviourProfiles>()
+            .init_asset_loader::<BehaviourProfilesLoader>()
+            .init_resource::<BehaviourRegistry>()
+            .init_resource::<BehaviourSettings>()
+            .add_systems(PreStartup, load_behaviour_profiles)
+            .add_systems(
+                FixedUpdate,
+                (
+                    apply_behaviour_profiles,
+                    update_behaviours.run_if(resource_exists::<CollisionGrid>),
+                )
+                    .chain()
+                    .in_set(FarmSet::Behaviour),
+            );
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::utils::BoxedFuture;
+use std::fmt;
+
+use super::components::BehaviourProfiles;
+
+#[derive(Debug)]
+pub enum BehaviourProfilesError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for BehaviourProfilesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BehaviourProfilesError::Io(error) => {
+                write!(f, "could not read behaviour profiles: {}", error)
+            }
+            BehaviourProfilesError::Json(error) => {
+                write!(f, "could not parse behaviour profiles: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BehaviourProfilesError {}
+
+#[derive(Default)]
+pub struct BehaviourProfilesLoader;
+
+impl AssetLoader for BehaviourProfilesLoader {
+    type Asset = BehaviourProfiles;
+    type Settings = ();
+    type Error = BehaviourProfilesError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<BehaviourProfiles, BehaviourProfilesError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(BehaviourProfilesError::Io)?;
+            serde_json::from_slice(&bytes).map_err(BehaviourProfilesError::Json)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
@@ -0,0 +1,250 @@
+use bevy::ecs::system::SystemParam;
+use rand::Rng;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::{
+    ActiveBehaviour, BehaviourAgent, BehaviourContext, BehaviourProfiles, BehaviourProfilesHandle,
+    BehaviourRegistry, BehaviourSettings, BehaviourStatus, BehaviourWeights, PetSnapshot,
+    BEHAVIOUR_PROFILES_PATH,
+};
+use crate::gameplay::clock::components::GameClock;
+use crate::gameplay::components::{
+    Animation, BasicEntityCollider, EntityActionState, FarmTuning, GenericActions,
+    InteractionTimers, Roaming,
+};
+use crate::gameplay::dialogue::components::DialoguePartner;
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::needs::components::{Needs, NeedsSettings};
+use crate::gameplay::npc::components::NpcEntity;
+use crate::gameplay::player::components::{
+    ActiveEtherPet, EntityPairConversationState, EtherPet, PlayerData, PlayerUniqueId,
+};
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::EntityRng;
+use crate::gameplay::tile::collision::CollisionGrid;
+use crate::gameplay::tile::components::LoadedMap;
+
+pub fn load_behaviour_profiles(mut commands: Commands, assets: Res<AssetServer>) {
+    let handle = assets.load(BEHAVIOUR_PROFILES_PATH);
+    commands.insert_resource(BehaviourProfilesHandle(handle));
+}
+
+// Pets get their weights once both they and the profiles are loaded, and
+// again whenever the profiles are edited with asset hot reloading
+pub fn apply_behaviour_profiles(
+    mut profile_events: EventReader<AssetEvent<BehaviourProfiles>>,
+    profiles_handle: Res<BehaviourProfilesHandle>,
+    profiles: Res<Assets<BehaviourProfiles>>,
+    mut pets_query: Query<(&PlayerData, &mut BehaviourWeights), With<EtherPet>>,
+) {
+    let profiles_changed = profile_events.read().any(|event| {
+        event.is_loaded_with_dependencies(&profiles_handle.0)
+            || event.is_modified(&profiles_handle.0)
+    });
+    let Some(profiles) = profiles.get(&profiles_handle.0) else {
+        return;
+    };
+
+    for (player_data, mut weights) in pets_query.iter_mut() {
+        if profiles_changed || weights.is_added() {
+            *weights = profiles.weights_for(&player_data.name);
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct BehaviourWorld<'w> {
+    registry: Res<'w, BehaviourRegistry>,
+    settings: Res<'w, BehaviourSettings>,
+    needs_settings: Res<'w, NeedsSettings>,
+    collision_grid: Res<'w, CollisionGrid>,
+    loaded_map: Res<'w, LoadedMap>,
+    relationship_graph: Res<'w, RelationshipGraph>,
+    entity_pair_convo_state: Res<'w, EntityPairConversationState>,
+    clock: Res<'w, GameClock>,
+    tuning: Res<'w, FarmTuning>,
+}
+
+impl BehaviourWorld<'_> {
+    fn in_conversation(&self, entity: Entity, interaction_timers: &InteractionTimers) -> bool {
+        interaction_timers.prox_chat_active
+            || *self
+                .entity_pair_convo_state
+                .entities_in_conversation
+                .get(&entity)
+                .unwrap_or(&false)
+    }
+}
+
+// Every so often each roaming pet scores all behaviours, weighted by its
+// profile, and switches to the best one. The running behaviour then moves
+// and animates the pet.
+pub fn update_behaviours(
+    time: Res<Time>,
+    world: BehaviourWorld,
+    mut pets_query: Query<
+        (
+            Entity,
+            &PlayerUniqueId,
+            &mut Transform,
+            &mut Animation,
+            &mut EntityActionState,
+            &mut ActiveBehaviour,
+            &BasicEntityCollider,
+            &Roaming,
+            &Needs,
+            &Mood,
+            &BehaviourWeights,
+            &InteractionTimers,
+            &mut EntityRng,
+        ),
+        (
+            With<NpcEntity>,
+            With<EtherPet>,
+            Without<ActiveEtherPet>,
+            // stands still while the player talks to it
+            Without<DialoguePartner>,
+        ),
+    >,
+    active_pet_query: Query<(Entity, &PlayerUniqueId, &Transform), With<ActiveEtherPet>>,
+) {
+    let mut others: Vec<PetSnapshot> = pets_query
+        .iter()
+        .map(
+            |(entity, unique_id, transform, _, _, active, _, _, _, _, _, interaction_timers, _)| {
+                PetSnapshot {
+                    entity,
+                    uuid: unique_id.uuid.clone(),
+                    position: transform.translation.truncate(),
+                    available: interaction_timers.can_prox_chat
+                        && !world.in_conversation(entity, interaction_timers)
+                        && !active.is_occupied(),
+                }
+            },
+        )
+        .collect();
+    // the player's pet can still be run from
+    others.extend(
+        active_pet_query
+            .iter()
+            .map(|(entity, unique_id, transform)| PetSnapshot {
+                entity,
+                uuid: unique_id.uuid.clone(),
+                position: transform.translation.truncate(),
+                available: false,
+            }),
+    );
+
+    let delta_seconds = time.delta_seconds();
+    for (
+        entity,
+        unique_id,
+        mut transform,
+        mut animation,
+        mut action_state,
+        mut active,
+        collider,
+        roaming,
+        needs,
+        mood,
+        weights,
+        interaction_timers,
+        mut rng,
+    ) in pets_query.iter_mut()
+    {
+        // the conversation systems move and animate chatting pets, whatever
+        // the pet was up to is dropped
+        if world.in_conversation(entity, interaction_timers) {
+            active.status = BehaviourStatus::Done;
+            continue;
+        }
+
+        active.tick(delta_seconds);
+        let context = BehaviourContext {
+            entity,
+            uuid: &unique_id.uuid,
+            position: transform.translation.truncate(),
+            current: (!active.is_finished()).then_some(active.kind),
+            needs,
+            mood,
+            can_chat: interaction_timers.can_prox_chat,
+            others: &others,
+            collision_grid: &world.collision_grid,
+            loaded_map: &world.loaded_map,
+            relationship_graph: &world.relationship_graph,
+            needs_settings: &world.needs_settings,
+            settings: &world.settings,
+            clock: &world.clock,
+            tuning: &world.tuning,
+        };
+        let mut agent = BehaviourAgent {
+            active: &mut active,
+            transform: &mut transform,
+            animation: &mut animation,
+            action_state: &mut action_state,
+            collider,
+            roaming,
+            rng: &mut rng,
+            delta_seconds,
+        };
+
+        if agent.active.is_finished()
+            || agent.active.since_evaluated >= world.settings.reevaluate_seconds
+        {
+            agent.active.since_evaluated = 0.0;
+            let noise = world.settings.score_noise;
+            let best = world
+                .registry
+                .behaviours
+                .iter()
+                .filter(|behaviour| !agent.active.is_cooling_down(behaviour.kind()))
+                .map(|behaviour| {
+                    let mut score = behaviour.score(&context)
+                        * weights.get(behaviour.kind())
+                        * agent.rng.gen_range(1.0 - noise..=1.0 + noise);
+                    if context.current == Some(behaviour.kind()) {
+                        score += world.settings.switch_margin;
+                    }
+                    (behaviour, score)
+                })
+                .filter(|(_, score)| *score > 0.0)
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(behaviour, _)| behaviour);
+
+            if let Some(behaviour) = best {
+                if context.current != Some(behaviour.kind()) {
+                    agent.active.begin(behaviour.kind());
+                    agent.active.status = behaviour.start(&mut agent, &context);
+                    if agent.active.status == BehaviourStatus::Failed {
+                        agent
+                            .active
+                            .cool_down(behaviour.kind(), world.settings.failed_cooldown_seconds);
+                    }
+                }
+            }
+        }
+
+        if agent.active.is_finished() {
+            continue;
+        }
+        let Some(behaviour) = world.registry.get(agent.active.kind) else {
+            continue;
+        };
+
+        agent.active.status = behaviour.execute(&mut agent, &context);
+        match agent.active.status {
+            BehaviourStatus::Running => {}
+            BehaviourStatus::Done => {
+                agent.action_state.action = GenericActions::Idle;
+            }
+            BehaviourStatus::Failed => {
+                agent.action_state.action = GenericActions::Idle;
+                agent
+                    .active
+                    .cool_down(behaviour.kind(), world.settings.failed_cooldown_seconds);
+            }
+        }
+    }
+}
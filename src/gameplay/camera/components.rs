@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+#[derive(Component, Debug)]
+pub struct MainCamera;
+
+#[derive(Resource, Debug, Clone)]
+pub struct CameraSettings {
+    // how quickly the camera catches up with the active pet, higher is snappier
+    pub follow_smoothing: f32,
+    // world units per second at CAMERA_SCALE_FACTOR zoom
+    pub pan_speed: f32,
+    // fraction of the current zoom per wheel line
+    pub zoom_step: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            follow_smoothing: 6.0,
+            pan_speed: TILE_SIZE * 20.0,
+            zoom_step: 0.1,
+            min_scale: CAMERA_SCALE_FACTOR * 0.5,
+            max_scale: CAMERA_SCALE_FACTOR * 3.0,
+        }
+    }
+}
+
+// cursor position of the last frame while the camera is being dragged
+#[derive(Resource, Debug, Default)]
+pub struct CameraDrag {
+    pub last_cursor_position: Option<Vec2>,
+}
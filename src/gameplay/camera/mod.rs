@@ -0,0 +1,37 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::state::components::CameraState;
+use super::tile::components::LoadedMap;
+
+mod systems;
+use systems::{
+    camera_follow_player, clamp_camera_to_map, manual_camera_drag, manual_camera_pan,
+    manual_camera_zoom, reset_main_menu_camera, spawn_camera,
+};
+
+pub mod components;
+use components::{CameraDrag, CameraSettings};
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraSettings>()
+            .init_resource::<CameraDrag>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(OnEnter(CameraState::MainMenuCamera), reset_main_menu_camera)
+            .add_systems(
+                Update,
+                (
+                    camera_follow_player.run_if(in_state(CameraState::FollowPlayer)),
+                    (manual_camera_pan, manual_camera_drag, manual_camera_zoom)
+                        .run_if(in_state(CameraState::ManualCameraControl)),
+                    clamp_camera_to_map.run_if(resource_exists::<LoadedMap>),
+                )
+                    .chain()
+                    // follows the pet where it's drawn, between ticks
+                    .after(FarmSet::Interpolate),
+            );
+    }
+}
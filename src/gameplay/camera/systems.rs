@@ -0,0 +1,173 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::camera::components::{CameraDrag, CameraSettings, MainCamera};
+use crate::gameplay::player::components::ActiveEtherPet;
+use crate::gameplay::tile::components::LoadedMap;
+
+// how many pixels of a pixel-precise scroll count as one wheel line
+const PIXELS_PER_SCROLL_LINE: f32 = 100.0;
+
+pub fn spawn_camera(mut commands: Commands) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scale = CAMERA_SCALE_FACTOR;
+
+    commands
+        .spawn(camera)
+        .insert(Name::new("Main Camera"))
+        .insert(MainCamera);
+}
+
+// the main menu always shows the middle of the farm at the default zoom
+pub fn reset_main_menu_camera(
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    for (mut transform, mut projection) in camera_query.iter_mut() {
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        projection.scale = CAMERA_SCALE_FACTOR;
+    }
+}
+
+// the camera keeps wall-clock pace whatever the simulation speed
+pub fn camera_follow_player(
+    time: Res<Time<Real>>,
+    camera_settings: Res<CameraSettings>,
+    player_query: Query<&Transform, (With<ActiveEtherPet>, Without<MainCamera>)>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    // frame rate independent exponential smoothing
+    let blend = 1.0 - (-camera_settings.follow_smoothing * time.delta_seconds()).exp();
+    for mut camera_transform in camera_query.iter_mut() {
+        let target = player_transform.translation.truncate();
+        let position = camera_transform.translation.truncate().lerp(target, blend);
+        camera_transform.translation.x = position.x;
+        camera_transform.translation.y = position.y;
+    }
+}
+
+pub fn manual_camera_pan(
+    time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    camera_settings: Res<CameraSettings>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let mut direction = Vec2::ZERO;
+    if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
+        direction.y += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
+        direction.y -= 1.0;
+    }
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    for (mut transform, projection) in camera_query.iter_mut() {
+        // pan the same distance on screen whatever the zoom
+        let zoom = projection.scale / CAMERA_SCALE_FACTOR;
+        let delta = direction.normalize() * camera_settings.pan_speed * zoom * time.delta_seconds();
+        transform.translation.x += delta.x;
+        transform.translation.y += delta.y;
+    }
+}
+
+// right or middle mouse drag moves the map with the cursor
+pub fn manual_camera_drag(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_drag: ResMut<CameraDrag>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let dragging = mouse_button_input.pressed(MouseButton::Right)
+        || mouse_button_input.pressed(MouseButton::Middle);
+    let cursor_position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let (true, Some(cursor_position)) = (dragging, cursor_position) else {
+        camera_drag.last_cursor_position = None;
+        return;
+    };
+
+    if let Some(last_cursor_position) = camera_drag.last_cursor_position {
+        let cursor_delta = cursor_position - last_cursor_position;
+        for (mut transform, projection) in camera_query.iter_mut() {
+            // window y points down, world y points up
+            transform.translation.x -= cursor_delta.x * projection.scale;
+            transform.translation.y += cursor_delta.y * projection.scale;
+        }
+    }
+    camera_drag.last_cursor_position = Some(cursor_position);
+}
+
+pub fn manual_camera_zoom(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    camera_settings: Res<CameraSettings>,
+    mut camera_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    let scroll: f32 = mouse_wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_SCROLL_LINE,
+        })
+        .sum();
+    if scroll == 0.0 {
+        return;
+    }
+
+    for mut projection in camera_query.iter_mut() {
+        // scrolling up zooms in
+        let scale = projection.scale * (1.0 - camera_settings.zoom_step).powf(scroll);
+        projection.scale = scale.clamp(camera_settings.min_scale, camera_settings.max_scale);
+    }
+}
+
+// Keeps the view inside the map. A map smaller than the view is centered.
+pub fn clamp_camera_to_map(
+    loaded_map: Res<LoadedMap>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let map_bounds = loaded_map.world_bounds();
+
+    for (mut transform, projection) in camera_query.iter_mut() {
+        let half_view = Vec2::new(window.width(), window.height()) / 2.0 * projection.scale;
+
+        transform.translation.x = clamp_axis(
+            transform.translation.x,
+            map_bounds.min.x + half_view.x,
+            map_bounds.max.x - half_view.x,
+        );
+        transform.translation.y = clamp_axis(
+            transform.translation.y,
+            map_bounds.min.y + half_view.y,
+            map_bounds.max.y - half_view.y,
+        );
+    }
+}
+
+fn clamp_axis(value: f32, min: f32, max: f32) -> f32 {
+    if min > max {
+        (min + max) / 2.0
+    } else {
+        value.clamp(min, max)
+    }
+}
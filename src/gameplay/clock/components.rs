@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+pub const HOURS_PER_DAY: f32 = 24.0;
+
+// (hour, tint) keyframes the scene tint blends between, alpha is how much
+// of the scene the tint covers
+const TINT_KEYFRAMES: [(f32, [f32; 4]); 8] = [
+    (0.0, [0.05, 0.05, 0.25, 0.5]),
+    (5.0, [0.05, 0.05, 0.25, 0.5]),
+    (6.5, [1.0, 0.6, 0.35, 0.18]),
+    (8.5, [1.0, 1.0, 1.0, 0.0]),
+    (17.5, [1.0, 1.0, 1.0, 0.0]),
+    (19.0, [0.95, 0.45, 0.3, 0.22]),
+    (21.0, [0.05, 0.05, 0.25, 0.5]),
+    (24.0, [0.05, 0.05, 0.25, 0.5]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl DayPhase {
+    pub fn from_hour(hour: f32) -> Self {
+        match hour {
+            hour if (5.0..8.0).contains(&hour) => DayPhase::Dawn,
+            hour if (8.0..18.0).contains(&hour) => DayPhase::Day,
+            hour if (18.0..21.0).contains(&hour) => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "Dawn",
+            DayPhase::Day => "Day",
+            DayPhase::Dusk => "Dusk",
+            DayPhase::Night => "Night",
+        }
+    }
+}
+
+// In-game time of day. Only runs while playing, so pausing stops the clock.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameClock {
+    // real seconds one in-game day lasts
+    pub day_length_seconds: f32,
+    // counts from 1
+    pub day: u32,
+    // 0.0 midnight to 24.0 the next midnight
+    pub hour: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            day_length_seconds: 480.0,
+            day: 1,
+            hour: 8.0,
+        }
+    }
+}
+
+impl GameClock {
+    pub fn tick(&mut self, delta_seconds: f32) {
+        self.hour += delta_seconds / self.day_length_seconds.max(1.0) * HOURS_PER_DAY;
+        while self.hour >= HOURS_PER_DAY {
+            self.hour -= HOURS_PER_DAY;
+            self.day += 1;
+        }
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        DayPhase::from_hour(self.hour)
+    }
+
+    pub fn is_night(&self) -> bool {
+        self.phase() == DayPhase::Night
+    }
+
+    // "Day 2  07:45  Dawn"
+    pub fn label(&self) -> String {
+        let minutes = (self.hour * 60.0) as u32;
+        format!(
+            "Day {}  {:02}:{:02}  {}",
+            self.day,
+            minutes / 60,
+            minutes % 60,
+            self.phase().label()
+        )
+    }
+
+    // how eager pets are to gossip, peaking around midday and barely at all
+    // in the middle of the night
+    pub fn chattiness(&self) -> f32 {
+        let from_midday = (self.hour - 13.0).abs() / 12.0;
+        0.4 + 1.1 * (1.0 - from_midday).powi(2)
+    }
+
+    pub fn tint(&self) -> Color {
+        let next = TINT_KEYFRAMES
+            .iter()
+            .position(|(hour, _)| *hour > self.hour)
+            .unwrap_or(TINT_KEYFRAMES.len() - 1)
+            .max(1);
+        let (from_hour, from) = TINT_KEYFRAMES[next - 1];
+        let (to_hour, to) = TINT_KEYFRAMES[next];
+        let blend = ((self.hour - from_hour) / (to_hour - from_hour)).clamp(0.0, 1.0);
+
+        let [r, g, b, a] =
+            std::array::from_fn(|index| from[index] + (to[index] - from[index]) * blend);
+        Color::rgba(r, g, b, a)
+    }
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::state::simulation_running;
+
+mod systems;
+use systems::tick_game_clock;
+
+pub mod components;
+use components::GameClock;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>().add_systems(
+            FixedUpdate,
+            tick_game_clock
+                .run_if(simulation_running)
+                .in_set(FarmSet::Clock),
+        );
+    }
+}
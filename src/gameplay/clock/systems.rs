@@ -0,0 +1,8 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::clock::components::GameClock;
+
+pub fn tick_game_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    clock.tick(time.delta_seconds());
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug)]
 pub struct EntityActionState {
@@ -44,23 +45,11 @@ pub struct Movement {
 
 #[derive(Component, Debug)]
 pub struct Roaming {
-    // walking
-    pub roam_direction: Vec3,
-    pub roam_time: f32,
-    pub roam_max_time: f32,
     pub roam_speed: f32,
-    // idle
-    pub idle_time: f32,
+    // longest a single stretch of roaming or idling lasts, see `BehaviourSettings`
+    // for the shortest
+    pub roam_max_time: f32,
     pub idle_max_time: f32,
-    pub is_idle: bool,
-    // eating
-    pub eating_time: f32,
-    pub eating_max_time: f32,
-    pub is_eating: bool,
-    // emote
-    pub emote_time: f32,
-    pub emote_max_time: f32,
-    pub is_emoting: bool,
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
@@ -79,6 +68,23 @@ pub enum DirectionIntent {
     Down,
 }
 
+impl DirectionIntent {
+    // the way a sprite should face when moving along `direction`
+    pub fn from_direction(direction: Vec2) -> Self {
+        if direction.x.abs() >= direction.y.abs() && direction.x != 0.0 {
+            if direction.x > 0.0 {
+                DirectionIntent::Right
+            } else {
+                DirectionIntent::Left
+            }
+        } else if direction.y > 0.0 {
+            DirectionIntent::Up
+        } else {
+            DirectionIntent::Down
+        }
+    }
+}
+
 #[derive(Component, Debug, Reflect, Resource, Default, PartialEq)]
 pub enum GenericActions {
     #[default]
@@ -106,6 +112,9 @@ pub struct InteractionTimers {
 #[derive(Component, Debug)]
 pub struct ChattingIconButton {
     pub chatting_icon_despawn_timer: Timer,
+    // the pair chatting under the icon
+    pub entity_a_uuid: String,
+    pub entity_b_uuid: String,
 }
 
 #[derive(Component, Debug)]
@@ -118,3 +127,55 @@ pub struct IconAnimation {
     pub current_frame: usize,
     pub frame_count: usize,
 }
+
+// The knobs batch runs compare. The game plays the defaults, gossip-sim-batch
+// can load others with --tuning, any field left out keeps its default.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct FarmTuning {
+    // how close two pets have to be to strike up a conversation
+    pub convo_distance: f32,
+    pub convo_duration: f32,
+    // how long a pet waits after a conversation before the next one
+    pub chat_cooldown_seconds: f32,
+    // longest a single stretch of roaming or idling lasts
+    pub roam_max_time: f32,
+    pub idle_max_time: f32,
+}
+
+impl Default for FarmTuning {
+    fn default() -> Self {
+        Self {
+            convo_distance: 15.0,
+            convo_duration: 10.0,
+            chat_cooldown_seconds: 15.0,
+            roam_max_time: 5.0,
+            idle_max_time: 2.0,
+        }
+    }
+}
+
+// The order the farm's systems run in, each plugin's systems chained within
+// their set. Bevy would otherwise pick a different order for systems
+// touching the same data every run, and a seed couldn't replay the farm.
+// Movement, timers and AI run on the fixed tick, the rest every frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FarmSet {
+    // Update
+    Load,
+    Input,
+    Dialogue,
+    // saving works while paused, when no ticks run
+    Save,
+    Interpolate,
+    // FixedUpdate
+    Clock,
+    Needs,
+    Behaviour,
+    Pets,
+    Cows,
+    Conversation,
+    Relationships,
+    Gossip,
+    Mood,
+}
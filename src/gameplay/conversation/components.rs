@@ -0,0 +1,69 @@
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+use crate::http_request::components::{ConversationResponse, HttpRequest};
+
+use super::providers::{HttpConversationProvider, OfflineConversationProvider};
+
+// `offline` (the default) makes up conversations locally, `http` asks the
+// backend at `GOSSIP_API_URL`
+pub const CONVERSATION_PROVIDER_ENV: &str = "GOSSIP_CONVERSATION_PROVIDER";
+
+#[derive(Debug, Clone)]
+pub struct ConversationParticipant {
+    pub uuid: String,
+    pub name: String,
+    pub emotion: BasicEmotions,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConversationRequest {
+    pub entity_a: ConversationParticipant,
+    pub entity_b: ConversationParticipant,
+    // drawn from `SimRng` for providers that make up the outcome themselves
+    pub roll: u64,
+}
+
+pub enum ConversationReply {
+    // the provider answered right away
+    Ready(ConversationResponse),
+    // the provider needs a round trip to the backend, the response comes
+    // back through the HttpRequestPlugin
+    Pending(HttpRequest),
+}
+
+pub trait ConversationProvider: Send + Sync {
+    fn converse(&mut self, request: &ConversationRequest) -> ConversationReply;
+}
+
+#[derive(Resource)]
+pub struct ConversationProviderResource(pub Box<dyn ConversationProvider>);
+
+impl ConversationProviderResource {
+    pub fn new(provider: impl ConversationProvider + 'static) -> Self {
+        Self(Box::new(provider))
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "offline" => Some(Self::new(OfflineConversationProvider::default())),
+            "http" => Some(Self::new(HttpConversationProvider::default())),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ConversationProviderResource {
+    fn default() -> Self {
+        let Ok(name) = std::env::var(CONVERSATION_PROVIDER_ENV) else {
+            return Self::new(OfflineConversationProvider::default());
+        };
+        Self::from_name(&name).unwrap_or_else(|| {
+            warn!(
+                "Unknown {} {:?}, expected offline or http",
+                CONVERSATION_PROVIDER_ENV, name
+            );
+            Self::new(OfflineConversationProvider::default())
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::queue_pair_entity_conversation_request;
+
+pub mod components;
+use components::ConversationProviderResource;
+
+pub mod providers;
+
+pub struct ConversationPlugin;
+
+impl Plugin for ConversationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConversationProviderResource>()
+            .add_systems(
+                FixedUpdate,
+                queue_pair_entity_conversation_request.in_set(FarmSet::Conversation),
+            );
+    }
+}
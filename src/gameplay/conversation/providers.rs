@@ -0,0 +1,362 @@
+use serde_json::json;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::conversation::components::{
+    ConversationParticipant, ConversationProvider, ConversationReply, ConversationRequest,
+};
+use crate::gameplay::player::components::BasicEmotions;
+use crate::gameplay::rng::components::stable_hash;
+use crate::http_request::components::{
+    ConversationApiConfig, ConversationEmote, ConversationLine, ConversationResponse, HttpMethod,
+    HttpRequest,
+};
+
+pub fn opening_lines(emotion: BasicEmotions) -> &'static [&'static str] {
+    match emotion {
+        BasicEmotions::Happiness => &[
+            "Hey {other}! Lovely day on the farm, isn't it?",
+            "{other}! I was hoping I'd bump into you.",
+            "Morning {other}, the apples smell amazing today.",
+        ],
+        BasicEmotions::Excitement => &[
+            "{other}! {other}! You won't believe what I just saw by the pond!",
+            "Guess what, {other}? Something big is happening on the farm!",
+            "Oh {other}, I can barely stand still today!",
+        ],
+        BasicEmotions::Sadness => &[
+            "Oh... hi {other}.",
+            "{other}, do you ever feel like nobody notices you around here?",
+            "I miss how things used to be, {other}.",
+        ],
+        BasicEmotions::Fear => &[
+            "{other}, did you hear that noise near the fences?",
+            "Please tell me you saw something odd by the trees too, {other}.",
+            "I don't like walking around alone lately, {other}.",
+        ],
+        BasicEmotions::Disgust => &[
+            "Ugh, {other}, have you smelled the water by the house?",
+            "{other}, someone left a mess near the soil patch again.",
+            "Don't stand so close, {other}.",
+        ],
+        BasicEmotions::Hate => &[
+            "What do you want, {other}?",
+            "Oh great. It's {other}.",
+            "I've got nothing to say to you, {other}.",
+        ],
+    }
+}
+
+pub fn reply_lines(emotion: BasicEmotions) -> &'static [&'static str] {
+    match emotion {
+        BasicEmotions::Happiness => &[
+            "It really is! Want to walk to the apple trees later?",
+            "Ha, you always know how to cheer me up.",
+            "I'm glad we ran into each other.",
+        ],
+        BasicEmotions::Excitement => &[
+            "No way! Tell me everything!",
+            "Really?! Let's go look right now!",
+            "That's the best thing I've heard all day!",
+        ],
+        BasicEmotions::Sadness => &[
+            "I guess so...",
+            "Sorry, I'm not much company today.",
+            "Everything just feels a bit grey lately.",
+        ],
+        BasicEmotions::Fear => &[
+            "D-don't say that, you're scaring me.",
+            "Maybe we should stick together for a while.",
+            "I'd rather not think about it.",
+        ],
+        BasicEmotions::Disgust => &[
+            "Gross. Don't remind me.",
+            "I can't believe anyone puts up with that.",
+            "Yuck, let's talk about something else.",
+        ],
+        BasicEmotions::Hate => &[
+            "Whatever. Leave me alone.",
+            "I don't care.",
+            "You're not worth my time.",
+        ],
+    }
+}
+
+// How a pet feels after talking to someone, based on its own emotion and
+// the emotion of the pet it talked to
+fn emotion_outcome(own: BasicEmotions, other: BasicEmotions, roll: u64) -> BasicEmotions {
+    match (own, other) {
+        (BasicEmotions::Happiness, BasicEmotions::Happiness)
+        | (BasicEmotions::Happiness, BasicEmotions::Excitement)
+        | (BasicEmotions::Excitement, BasicEmotions::Happiness) => match roll % 2 {
+            0 => BasicEmotions::Excitement,
+            _ => BasicEmotions::Happiness,
+        },
+        (BasicEmotions::Excitement, BasicEmotions::Excitement) => BasicEmotions::Excitement,
+        (own, BasicEmotions::Sadness) if own.is_positive() => BasicEmotions::Sadness,
+        (own, BasicEmotions::Fear) if own.is_positive() => BasicEmotions::Fear,
+        (own, BasicEmotions::Disgust) | (own, BasicEmotions::Hate) if own.is_positive() => {
+            BasicEmotions::Disgust
+        }
+        (BasicEmotions::Sadness, other) | (BasicEmotions::Fear, other) if other.is_positive() => {
+            BasicEmotions::Happiness
+        }
+        (BasicEmotions::Disgust, other) | (BasicEmotions::Hate, other) if other.is_positive() => {
+            match roll % 3 {
+                0 => BasicEmotions::Happiness,
+                _ => own,
+            }
+        }
+        (BasicEmotions::Hate, _) | (_, BasicEmotions::Hate) => BasicEmotions::Hate,
+        (BasicEmotions::Fear, _) | (_, BasicEmotions::Fear) => BasicEmotions::Fear,
+        _ => own,
+    }
+}
+
+fn pick<'a>(lines: &'a [&'a str], roll: u64) -> &'a str {
+    lines[(roll % lines.len() as u64) as usize]
+}
+
+fn say(
+    speaker: &ConversationParticipant,
+    listener: &ConversationParticipant,
+    template: &str,
+) -> ConversationLine {
+    ConversationLine {
+        speaker_uuid: speaker.uuid.clone(),
+        text: template.replace("{other}", &listener.name),
+    }
+}
+
+// Builds dialogue and emotion outcomes locally from each pet's PlayerData
+// and the request's roll, so the same seed replays the same conversations.
+#[derive(Default, Debug)]
+pub struct OfflineConversationProvider {
+    pub conversation_count: HashMap<(String, String), u64>,
+}
+
+impl OfflineConversationProvider {
+    pub fn build_response(
+        &mut self,
+        entity_a: &ConversationParticipant,
+        entity_b: &ConversationParticipant,
+        roll: u64,
+    ) -> ConversationResponse {
+        let pair_key = if entity_a.uuid <= entity_b.uuid {
+            (entity_a.uuid.clone(), entity_b.uuid.clone())
+        } else {
+            (entity_b.uuid.clone(), entity_a.uuid.clone())
+        };
+        let count = self.conversation_count.entry(pair_key).or_insert(0);
+        let roll = stable_hash(&[&entity_a.name, &entity_b.name], roll);
+        *count += 1;
+
+        let conversation = vec![
+            say(
+                entity_a,
+                entity_b,
+                pick(opening_lines(entity_a.emotion), roll),
+            ),
+            say(
+                entity_b,
+                entity_a,
+                pick(reply_lines(entity_b.emotion), roll >> 8),
+            ),
+            say(
+                entity_b,
+                entity_a,
+                pick(opening_lines(entity_b.emotion), roll >> 16),
+            ),
+            say(
+                entity_a,
+                entity_b,
+                pick(reply_lines(entity_a.emotion), roll >> 24),
+            ),
+        ];
+
+        let emotes = vec![
+            ConversationEmote {
+                entity_uuid: entity_a.uuid.clone(),
+                emote: emotion_outcome(entity_a.emotion, entity_b.emotion, roll >> 32)
+                    .as_str()
+                    .to_string(),
+            },
+            ConversationEmote {
+                entity_uuid: entity_b.uuid.clone(),
+                emote: emotion_outcome(entity_b.emotion, entity_a.emotion, roll >> 40)
+                    .as_str()
+                    .to_string(),
+            },
+        ];
+
+        ConversationResponse {
+            entity_a_uuid: entity_a.uuid.clone(),
+            entity_b_uuid: entity_b.uuid.clone(),
+            conversation,
+            emotes,
+        }
+    }
+}
+
+impl ConversationProvider for OfflineConversationProvider {
+    fn converse(&mut self, request: &ConversationRequest) -> ConversationReply {
+        ConversationReply::Ready(self.build_response(
+            &request.entity_a,
+            &request.entity_b,
+            request.roll,
+        ))
+    }
+}
+
+// Asks the conversation backend at `API_URL` for the dialogue
+#[derive(Default, Debug)]
+pub struct HttpConversationProvider {
+    pub api_config: ConversationApiConfig,
+}
+
+impl ConversationProvider for HttpConversationProvider {
+    fn converse(&mut self, request: &ConversationRequest) -> ConversationReply {
+        ConversationReply::Pending(HttpRequest {
+            url: self.api_config.converse_url(),
+            method: HttpMethod::POST,
+            headers: HashMap::new(),
+            body: json!({
+                "entity_a_uuid": request.entity_a.uuid,
+                "entity_b_uuid": request.entity_b.uuid,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::conversation::components::ConversationProviderResource;
+
+    fn participant(uuid: &str, name: &str, emotion: BasicEmotions) -> ConversationParticipant {
+        ConversationParticipant {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+            emotion,
+        }
+    }
+
+    fn request(roll: u64) -> ConversationRequest {
+        ConversationRequest {
+            entity_a: participant("a", "Bun", BasicEmotions::Happiness),
+            entity_b: participant("b", "Pip", BasicEmotions::Sadness),
+            roll,
+        }
+    }
+
+    fn converse(provider: &mut OfflineConversationProvider, roll: u64) -> ConversationResponse {
+        match provider.converse(&request(roll)) {
+            ConversationReply::Ready(response) => response,
+            ConversationReply::Pending(_) => panic!("offline provider made a request"),
+        }
+    }
+
+    fn transcript(response: &ConversationResponse) -> Vec<(String, String)> {
+        response
+            .conversation
+            .iter()
+            .map(|line| (line.speaker_uuid.clone(), line.text.clone()))
+            .chain(
+                response
+                    .emotes
+                    .iter()
+                    .map(|emote| (emote.entity_uuid.clone(), emote.emote.clone())),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn same_roll_gives_the_same_conversation() {
+        for roll in [0, 1, 42, u64::MAX] {
+            let first = converse(&mut OfflineConversationProvider::default(), roll);
+            let second = converse(&mut OfflineConversationProvider::default(), roll);
+            assert_eq!(transcript(&first), transcript(&second));
+        }
+    }
+
+    #[test]
+    fn conversation_follows_each_pets_data() {
+        let response = converse(&mut OfflineConversationProvider::default(), 7);
+        assert_eq!(response.entity_a_uuid, "a");
+        assert_eq!(response.entity_b_uuid, "b");
+
+        let speakers: Vec<_> = response
+            .conversation
+            .iter()
+            .map(|line| line.speaker_uuid.as_str())
+            .collect();
+        assert_eq!(speakers, vec!["a", "b", "b", "a"]);
+        assert!(opening_lines(BasicEmotions::Happiness)
+            .iter()
+            .any(|line| line.replace("{other}", "Pip") == response.conversation[0].text));
+        assert!(
+            reply_lines(BasicEmotions::Sadness).contains(&response.conversation[1].text.as_str())
+        );
+
+        let emotes: Vec<_> = response
+            .emotes
+            .iter()
+            .map(|emote| {
+                (
+                    emote.entity_uuid.as_str(),
+                    BasicEmotions::parse(&emote.emote).unwrap(),
+                )
+            })
+            .collect();
+        // a happy pet talking to a sad one comes away sad, and cheers it up
+        assert_eq!(
+            emotes,
+            vec![
+                ("a", BasicEmotions::Sadness),
+                ("b", BasicEmotions::Happiness)
+            ]
+        );
+    }
+
+    #[test]
+    fn pair_count_is_kept_whichever_pet_starts() {
+        let mut provider = OfflineConversationProvider::default();
+        converse(&mut provider, 1);
+        provider.converse(&ConversationRequest {
+            entity_a: request(2).entity_b,
+            entity_b: request(2).entity_a,
+            roll: 2,
+        });
+        assert_eq!(
+            provider
+                .conversation_count
+                .get(&("a".to_string(), "b".to_string())),
+            Some(&2)
+        );
+    }
+
+    #[test]
+    fn http_provider_posts_to_the_configured_backend() {
+        let mut provider = HttpConversationProvider {
+            api_config: ConversationApiConfig {
+                base_url: "http://127.0.0.1:9/".to_string(),
+            },
+        };
+        match provider.converse(&request(0)) {
+            ConversationReply::Pending(http_request) => {
+                assert_eq!(http_request.url, "http://127.0.0.1:9/characters/converse");
+                assert_eq!(http_request.method, HttpMethod::POST);
+                assert_eq!(http_request.body["entity_b_uuid"], "b");
+            }
+            ConversationReply::Ready(_) => panic!("http provider answered without the backend"),
+        }
+    }
+
+    #[test]
+    fn provider_is_picked_by_name() {
+        assert!(ConversationProviderResource::from_name("offline").is_some());
+        assert!(ConversationProviderResource::from_name("HTTP").is_some());
+        assert!(ConversationProviderResource::from_name("carrier pigeon").is_none());
+    }
+}
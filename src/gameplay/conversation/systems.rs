@@ -0,0 +1,242 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::conversation::components::{
+    ConversationParticipant, ConversationProviderResource, ConversationReply, ConversationRequest,
+};
+use crate::gameplay::player::components::{
+    EntityPairConversationState, EtherPet, PlayerData, PlayerUniqueId,
+};
+use crate::gameplay::rng::components::SimRng;
+use crate::http_request::components::{
+    ConversationHistoryResource, PendingConversationHttpRequests,
+};
+
+// events
+use crate::gameplay::events::ConversationActionEvent;
+use crate::http_request::events::ConversationHistoryEvent;
+
+fn get_participant(
+    entity: Entity,
+    participants_query: &Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+) -> Option<ConversationParticipant> {
+    participants_query
+        .get(entity)
+        .ok()
+        .map(|(unique_id, player_data)| ConversationParticipant {
+            uuid: unique_id.uuid.clone(),
+            name: player_data.name.clone(),
+            emotion: player_data.emotion,
+        })
+}
+
+pub fn queue_pair_entity_conversation_request(
+    mut action_event_reader: EventReader<ConversationActionEvent>,
+    mut provider: ResMut<ConversationProviderResource>,
+    mut sim_rng: ResMut<SimRng>,
+    mut entity_pair_convo_state: ResMut<EntityPairConversationState>,
+    mut pending_requests: ResMut<PendingConversationHttpRequests>,
+    mut conversation_history: ResMut<ConversationHistoryResource>,
+    mut conversation_history_event_writer: EventWriter<ConversationHistoryEvent>,
+    participants_query: Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+) {
+    for event in action_event_reader.read() {
+        let (Some(entity_a), Some(entity_b)) = (
+            get_participant(event.entity_a, &participants_query),
+            get_participant(event.entity_b, &participants_query),
+        ) else {
+            continue;
+        };
+
+        let request = ConversationRequest {
+            entity_a,
+            entity_b,
+            roll: sim_rng.gen(),
+        };
+        match provider.0.converse(&request) {
+            ConversationReply::Ready(response) => {
+                conversation_history.record(response.clone());
+                conversation_history_event_writer.send(ConversationHistoryEvent { response });
+            }
+            ConversationReply::Pending(request) => {
+                pending_requests.requests.push(request);
+            }
+        }
+
+        if let Some(pair_convo_data) = entity_pair_convo_state
+            .pair_entities_in_conversation
+            .get_mut(&(event.entity_a, event.entity_b))
+        {
+            pair_convo_data.conversation_http_request_sent = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::conversation::components::ConversationProvider;
+    use crate::gameplay::player::components::{
+        BasicEmotions, PairEntityConversationData, Quadrants,
+    };
+    use crate::http_request::components::{
+        ConversationEmote, ConversationResponse, HttpMethod, HttpRequest,
+    };
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    // Answers from a script and remembers who it was asked about
+    struct FakeConversationProvider {
+        pending: bool,
+        requests: Arc<Mutex<Vec<ConversationRequest>>>,
+    }
+
+    impl ConversationProvider for FakeConversationProvider {
+        fn converse(&mut self, request: &ConversationRequest) -> ConversationReply {
+            self.requests.lock().unwrap().push(request.clone());
+            if self.pending {
+                return ConversationReply::Pending(HttpRequest {
+                    url: "http://fake/characters/converse".to_string(),
+                    method: HttpMethod::POST,
+                    headers: HashMap::new(),
+                    body: serde_json::Value::Null,
+                });
+            }
+            ConversationReply::Ready(ConversationResponse {
+                entity_a_uuid: request.entity_a.uuid.clone(),
+                entity_b_uuid: request.entity_b.uuid.clone(),
+                conversation: Vec::new(),
+                emotes: vec![ConversationEmote {
+                    entity_uuid: request.entity_b.uuid.clone(),
+                    emote: "fear".to_string(),
+                }],
+            })
+        }
+    }
+
+    fn spawn_pet(app: &mut App, uuid: &str, name: &str, emotion: BasicEmotions) -> Entity {
+        app.world
+            .spawn((
+                EtherPet,
+                PlayerUniqueId {
+                    uuid: uuid.to_string(),
+                },
+                PlayerData {
+                    name: name.to_string(),
+                    attack_dmg: 0,
+                    speed: 1.0,
+                    player_id: uuid.to_string(),
+                    emotion,
+                },
+            ))
+            .id()
+    }
+
+    // two pets already walking up to each other, and one conversation action
+    fn run_conversation(pending: bool) -> (App, Vec<ConversationRequest>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.insert_resource(ConversationProviderResource::new(
+            FakeConversationProvider {
+                pending,
+                requests: requests.clone(),
+            },
+        ))
+        .insert_resource(SimRng::new(3))
+        .init_resource::<EntityPairConversationState>()
+        .init_resource::<PendingConversationHttpRequests>()
+        .init_resource::<ConversationHistoryResource>()
+        .add_event::<ConversationActionEvent>()
+        .add_event::<ConversationHistoryEvent>()
+        .add_systems(Update, queue_pair_entity_conversation_request);
+
+        let entity_a = spawn_pet(&mut app, "a", "Bun", BasicEmotions::Happiness);
+        let entity_b = spawn_pet(&mut app, "b", "Pip", BasicEmotions::Hate);
+        app.world
+            .resource_mut::<EntityPairConversationState>()
+            .pair_entities_in_conversation
+            .insert(
+                (entity_a, entity_b),
+                PairEntityConversationData {
+                    entity_a,
+                    entity_b,
+                    entity_a_pos: Vec3::ZERO,
+                    entity_b_pos: Vec3::ZERO,
+                    entity_a_id: "a".to_string(),
+                    entity_b_id: "b".to_string(),
+                    quadrant: Quadrants::None,
+                    chatting_icon_spawned: false,
+                    conversation_http_request_sent: false,
+                    path: VecDeque::new(),
+                },
+            );
+        app.world
+            .send_event(ConversationActionEvent { entity_a, entity_b });
+        app.update();
+
+        let requests = requests.lock().unwrap().clone();
+        (app, requests)
+    }
+
+    #[test]
+    fn provider_is_asked_with_each_pets_data() {
+        let (app, requests) = run_conversation(false);
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(
+            (request.entity_a.name.as_str(), request.entity_a.emotion),
+            ("Bun", BasicEmotions::Happiness)
+        );
+        assert_eq!(
+            (request.entity_b.name.as_str(), request.entity_b.emotion),
+            ("Pip", BasicEmotions::Hate)
+        );
+        // the roll comes from the seeded SimRng
+        assert_eq!(request.roll, SimRng::new(3).gen::<u64>());
+
+        let pair = app
+            .world
+            .resource::<EntityPairConversationState>()
+            .pair_entities_in_conversation
+            .values()
+            .next()
+            .unwrap()
+            .clone();
+        assert!(pair.conversation_http_request_sent);
+    }
+
+    #[test]
+    fn ready_reply_is_recorded_right_away() {
+        let (app, _) = run_conversation(false);
+        let history = app.world.resource::<ConversationHistoryResource>();
+        assert_eq!(history.conversations.len(), 1);
+        assert_eq!(history.entities_to_emote[0].entity_uuid, "b");
+        assert_eq!(
+            app.world
+                .resource::<Events<ConversationHistoryEvent>>()
+                .len(),
+            1
+        );
+        assert!(app
+            .world
+            .resource::<PendingConversationHttpRequests>()
+            .requests
+            .is_empty());
+    }
+
+    #[test]
+    fn pending_reply_is_queued_for_the_backend() {
+        let (app, _) = run_conversation(true);
+        assert!(app
+            .world
+            .resource::<ConversationHistoryResource>()
+            .conversations
+            .is_empty());
+        let pending = &app
+            .world
+            .resource::<PendingConversationHttpRequests>()
+            .requests;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].url, "http://fake/characters/converse");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+
+// how close the active pet has to be to strike up a conversation
+pub const INTERACT_DISTANCE: f32 = 30.0;
+
+// the pet the player is talking to, it stands still until the dialogue ends
+#[derive(Component, Debug)]
+pub struct DialoguePartner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogueChoice {
+    Compliment,
+    AskHowTheyAre,
+    ShareNews,
+    Tease,
+    Insult,
+}
+
+impl DialogueChoice {
+    pub const ALL: [DialogueChoice; 5] = [
+        DialogueChoice::Compliment,
+        DialogueChoice::AskHowTheyAre,
+        DialogueChoice::ShareNews,
+        DialogueChoice::Tease,
+        DialogueChoice::Insult,
+    ];
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            DialogueChoice::Compliment => "You're looking great today!",
+            DialogueChoice::AskHowTheyAre => "How are you holding up?",
+            DialogueChoice::ShareNews => "Guess what I just saw by the pond!",
+            DialogueChoice::Tease => "Is that mud on your face again?",
+            DialogueChoice::Insult => "Nobody on this farm likes you.",
+        }
+    }
+
+    // how the active pet comes across
+    pub fn tone(&self) -> BasicEmotions {
+        match self {
+            DialogueChoice::Compliment | DialogueChoice::AskHowTheyAre => BasicEmotions::Happiness,
+            DialogueChoice::ShareNews | DialogueChoice::Tease => BasicEmotions::Excitement,
+            DialogueChoice::Insult => BasicEmotions::Hate,
+        }
+    }
+
+    // how the other pet takes it, depending on the mood it was in
+    pub fn reaction(&self, emotion: BasicEmotions) -> BasicEmotions {
+        match (self, emotion) {
+            (DialogueChoice::Compliment, emotion) if emotion.is_hostile() => BasicEmotions::Disgust,
+            (DialogueChoice::Compliment, _) => BasicEmotions::Happiness,
+            (DialogueChoice::AskHowTheyAre, BasicEmotions::Sadness | BasicEmotions::Fear) => {
+                BasicEmotions::Happiness
+            }
+            (DialogueChoice::AskHowTheyAre, BasicEmotions::Hate) => BasicEmotions::Disgust,
+            (DialogueChoice::AskHowTheyAre, emotion) => emotion,
+            (DialogueChoice::ShareNews, BasicEmotions::Fear | BasicEmotions::Hate) => emotion,
+            (DialogueChoice::ShareNews, _) => BasicEmotions::Excitement,
+            (DialogueChoice::Tease, BasicEmotions::Happiness | BasicEmotions::Excitement) => {
+                BasicEmotions::Happiness
+            }
+            (DialogueChoice::Tease, BasicEmotions::Sadness | BasicEmotions::Fear) => {
+                BasicEmotions::Sadness
+            }
+            (DialogueChoice::Tease, _) => BasicEmotions::Hate,
+            (DialogueChoice::Insult, BasicEmotions::Sadness | BasicEmotions::Fear) => {
+                BasicEmotions::Fear
+            }
+            (DialogueChoice::Insult, _) => BasicEmotions::Hate,
+        }
+    }
+}
+
+// The conversation between the active pet and the pet it walked up to
+#[derive(Resource, Debug, Clone)]
+pub struct MainDialogueConversation {
+    pub active_entity: Entity,
+    pub partner_entity: Entity,
+    pub active_uuid: String,
+    pub partner_uuid: String,
+    pub active_name: String,
+    pub partner_name: String,
+    // what the partner just said
+    pub partner_line: String,
+    pub revealed_chars: f32,
+    // the conversation is over once a choice has been answered
+    pub choice: Option<DialogueChoice>,
+}
@@ -0,0 +1,8 @@
+use crate::prelude::*;
+
+use crate::gameplay::dialogue::components::DialogueChoice;
+
+#[derive(Event, Debug)]
+pub struct DialogueChoiceEvent {
+    pub choice: DialogueChoice,
+}
@@ -0,0 +1,32 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::state::components::{CameraState, GameState};
+
+mod systems;
+use systems::{apply_dialogue_choice, end_main_dialogue, start_main_dialogue};
+
+pub mod components;
+
+pub mod events;
+use events::DialogueChoiceEvent;
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DialogueChoiceEvent>()
+            .add_systems(
+                Update,
+                (
+                    start_main_dialogue.run_if(
+                        in_state(GameState::Playing).and_then(in_state(CameraState::FollowPlayer)),
+                    ),
+                    apply_dialogue_choice.run_if(in_state(GameState::MainDialogue)),
+                )
+                    .chain()
+                    .in_set(FarmSet::Dialogue),
+            )
+            .add_systems(OnExit(GameState::MainDialogue), end_main_dialogue);
+    }
+}
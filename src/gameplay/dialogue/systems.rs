@@ -0,0 +1,204 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::ActiveBehaviour;
+use crate::gameplay::components::{EntityActionState, GenericActions, InteractionTimers};
+use crate::gameplay::conversation::providers::{opening_lines, reply_lines};
+use crate::gameplay::dialogue::components::{
+    DialogueChoice, DialoguePartner, MainDialogueConversation, INTERACT_DISTANCE,
+};
+use crate::gameplay::mood::components::{Mood, MoodSettings};
+use crate::gameplay::npc::components::NpcEntity;
+use crate::gameplay::player::components::{
+    ActiveEtherPet, EntityPairConversationState, EtherPet, PlayerData, PlayerUniqueId,
+};
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::SimRng;
+use crate::gameplay::state::components::GameState;
+use crate::http_request::components::{
+    ConversationEmote, ConversationHistoryResource, ConversationLine, ConversationResponse,
+};
+
+// events
+use crate::gameplay::dialogue::events::DialogueChoiceEvent;
+use crate::gameplay::player::events::EmoteEvent;
+
+fn random_line(lines: &[&str], listener_name: &str, rng: &mut impl Rng) -> String {
+    lines
+        .choose(rng)
+        .copied()
+        .unwrap_or_default()
+        .replace("{other}", listener_name)
+}
+
+// The active pet strikes up a conversation with the closest pet that isn't
+// already busy talking
+pub fn start_main_dialogue(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    entity_pair_convo_state: Res<EntityPairConversationState>,
+    mut sim_rng: ResMut<SimRng>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    active_query: Query<(Entity, &Transform, &PlayerUniqueId, &PlayerData), With<ActiveEtherPet>>,
+    mut partner_query: Query<
+        (
+            Entity,
+            &Transform,
+            &PlayerUniqueId,
+            &PlayerData,
+            &Mood,
+            &InteractionTimers,
+            &ActiveBehaviour,
+            &mut EntityActionState,
+        ),
+        (With<EtherPet>, With<NpcEntity>, Without<ActiveEtherPet>),
+    >,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+    let Ok((active_entity, active_transform, active_unique_id, active_data)) =
+        active_query.get_single()
+    else {
+        return;
+    };
+
+    let Some(partner_entity) = partner_query
+        .iter()
+        .filter(|(entity, .., interaction_timers, active_behaviour, _)| {
+            !interaction_timers.prox_chat_active
+                && !active_behaviour.is_occupied()
+                && !*entity_pair_convo_state
+                    .entities_in_conversation
+                    .get(entity)
+                    .unwrap_or(&false)
+        })
+        .map(|(entity, transform, ..)| {
+            let distance = transform
+                .translation
+                .truncate()
+                .distance(active_transform.translation.truncate());
+            (distance, entity)
+        })
+        .filter(|(distance, _)| *distance <= INTERACT_DISTANCE)
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, entity)| entity)
+    else {
+        return;
+    };
+    let Ok((_, _, partner_unique_id, partner_data, partner_mood, .., mut partner_action_state)) =
+        partner_query.get_mut(partner_entity)
+    else {
+        return;
+    };
+
+    partner_action_state.action = GenericActions::Idle;
+    commands.entity(partner_entity).insert(DialoguePartner);
+
+    commands.insert_resource(MainDialogueConversation {
+        active_entity,
+        partner_entity,
+        active_uuid: active_unique_id.uuid.clone(),
+        partner_uuid: partner_unique_id.uuid.clone(),
+        active_name: active_data.name.clone(),
+        partner_name: partner_data.name.clone(),
+        // greets in the mood it's in now, the one its reaction comes from
+        partner_line: random_line(
+            opening_lines(partner_mood.dominant),
+            &active_data.name,
+            sim_rng.as_mut(),
+        ),
+        revealed_chars: 0.0,
+        choice: None,
+    });
+    next_game_state.set(GameState::MainDialogue);
+}
+
+// The partner reacts to the choice based on its mood, the reaction shifts
+// its mood and how both pets feel about each other
+pub fn apply_dialogue_choice(
+    mut dialogue_choice_event_reader: EventReader<DialogueChoiceEvent>,
+    main_dialogue_conversation: Option<ResMut<MainDialogueConversation>>,
+    mut relationship_graph: ResMut<RelationshipGraph>,
+    mut conversation_history: ResMut<ConversationHistoryResource>,
+    mood_settings: Res<MoodSettings>,
+    mut sim_rng: ResMut<SimRng>,
+    mut emote_event_writer: EventWriter<EmoteEvent>,
+    mut partner_query: Query<&mut Mood, With<DialoguePartner>>,
+) {
+    let Some(mut conversation) = main_dialogue_conversation else {
+        dialogue_choice_event_reader.clear();
+        return;
+    };
+
+    for event in dialogue_choice_event_reader.read() {
+        if conversation.choice.is_some() {
+            continue;
+        }
+        let Ok(mut partner_mood) = partner_query.get_mut(conversation.partner_entity) else {
+            continue;
+        };
+
+        let choice: DialogueChoice = event.choice;
+        let tone = choice.tone();
+        let reaction = choice.reaction(partner_mood.dominant);
+        partner_mood.shift(reaction, mood_settings.conversation_shift);
+
+        relationship_graph
+            .get_mut(&conversation.partner_uuid, &conversation.active_uuid)
+            .record_conversation(reaction, tone);
+        relationship_graph
+            .get_mut(&conversation.active_uuid, &conversation.partner_uuid)
+            .record_conversation(tone, reaction);
+
+        let reply = random_line(
+            reply_lines(reaction),
+            &conversation.active_name,
+            sim_rng.as_mut(),
+        );
+
+        // kept in the history so the exchange is saved with the rest
+        conversation_history
+            .conversations
+            .push(ConversationResponse {
+                entity_a_uuid: conversation.active_uuid.clone(),
+                entity_b_uuid: conversation.partner_uuid.clone(),
+                conversation: vec![
+                    ConversationLine {
+                        speaker_uuid: conversation.partner_uuid.clone(),
+                        text: conversation.partner_line.clone(),
+                    },
+                    ConversationLine {
+                        speaker_uuid: conversation.active_uuid.clone(),
+                        text: choice.text().to_string(),
+                    },
+                    ConversationLine {
+                        speaker_uuid: conversation.partner_uuid.clone(),
+                        text: reply.clone(),
+                    },
+                ],
+                emotes: vec![ConversationEmote {
+                    entity_uuid: conversation.partner_uuid.clone(),
+                    emote: reaction.as_str().to_string(),
+                }],
+            });
+        emote_event_writer.send(EmoteEvent {
+            entity_uuid: conversation.partner_uuid.clone(),
+            emote: reaction.as_str().to_string(),
+        });
+
+        conversation.partner_line = reply;
+        conversation.revealed_chars = 0.0;
+        conversation.choice = Some(choice);
+    }
+}
+
+pub fn end_main_dialogue(
+    mut commands: Commands,
+    partner_query: Query<Entity, With<DialoguePartner>>,
+) {
+    for partner_entity in partner_query.iter() {
+        commands.entity(partner_entity).remove::<DialoguePartner>();
+    }
+    commands.remove_resource::<MainDialogueConversation>();
+}
@@ -1,7 +1,23 @@
 use crate::prelude::*;
 
-#[derive(Debug)]
+#[derive(Event, Debug)]
 pub struct ConversationActionEvent {
     pub entity_a: Entity,
     pub entity_b: Entity,
 }
+
+// Two pets met up and talked until the chat timer ran out, sent before
+// they're released. Interrupted conversations never send it.
+#[derive(Event, Debug)]
+pub struct ConversationFinishedEvent {
+    pub entity_a: Entity,
+    pub entity_b: Entity,
+}
+
+// Two pets were close enough to chat but no free tile next to entity_a can
+// be walked to from entity_b
+#[derive(Event, Debug)]
+pub struct ConversationUnreachableEvent {
+    pub entity_a: Entity,
+    pub entity_b: Entity,
+}
@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Rumor {
+    // shared by every copy of the same rumor, however distorted
+    pub id: u64,
+    pub subject_uuid: String,
+    pub subject_name: String,
+    pub claim: String,
+    // -1.0 slander, 1.0 praise
+    pub sentiment: f32,
+    // uuid of the pet that made it up
+    pub origin_uuid: String,
+    // how many times it changed hands before reaching this pet
+    pub hops: u32,
+    // how many of those retellings changed it
+    pub distortions: u32,
+}
+
+impl Rumor {
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.subject_name, self.claim)
+    }
+}
+
+// The rumors a pet has heard or made up, oldest first
+#[derive(Component, Debug, Clone, Default)]
+pub struct RumorMemory {
+    pub rumors: VecDeque<Rumor>,
+}
+
+impl RumorMemory {
+    pub fn knows(&self, rumor_id: u64) -> bool {
+        self.rumors.iter().any(|rumor| rumor.id == rumor_id)
+    }
+
+    // forgets the oldest rumors once the pet can't keep track of any more
+    pub fn remember(&mut self, rumor: Rumor, capacity: usize) {
+        self.rumors.push_back(rumor);
+        while self.rumors.len() > capacity {
+            self.rumors.pop_front();
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct GossipSettings {
+    // chance a pet makes up a rumor about who it just talked to
+    pub invent_chance: f64,
+    // chance each rumor a pet knows is passed on in a conversation
+    pub share_chance: f64,
+    // chance a shared rumor gets twisted in the retelling
+    pub distortion_chance: f64,
+    // chance a shared rumor is lost on the way and not remembered
+    pub decay_chance: f64,
+    // rumors stop spreading after this many hops
+    pub max_hops: u32,
+    pub memory_capacity: usize,
+    // how much hearing a rumor sways the listener's affinity for its subject
+    pub affinity_influence: f32,
+}
+
+impl Default for GossipSettings {
+    fn default() -> Self {
+        Self {
+            invent_chance: 0.35,
+            share_chance: 0.5,
+            distortion_chance: 0.25,
+            decay_chance: 0.15,
+            max_hops: 8,
+            memory_capacity: 12,
+            affinity_influence: 0.05,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RumorTransfer {
+    pub rumor_id: u64,
+    pub from_uuid: String,
+    pub to_uuid: String,
+    // the rumor as the listener heard it
+    pub claim: String,
+    pub sentiment: f32,
+    pub hops: u32,
+    pub distorted: bool,
+}
+
+// Every rumor ever started and every time one changed hands, so a rumor's
+// path across the farm can be traced after the fact
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RumorLog {
+    pub next_rumor_id: u64,
+    pub origins: HashMap<u64, Rumor>,
+    pub transfers: Vec<RumorTransfer>,
+}
+
+impl RumorLog {
+    pub fn start_rumor(
+        &mut self,
+        origin_uuid: &str,
+        subject_uuid: &str,
+        subject_name: &str,
+        subject_emotion: BasicEmotions,
+    ) -> Rumor {
+        let rumor = Rumor {
+            id: self.next_rumor_id,
+            subject_uuid: subject_uuid.to_string(),
+            subject_name: subject_name.to_string(),
+            claim: claim_for_emotion(subject_emotion).to_string(),
+            sentiment: subject_emotion.valence(),
+            origin_uuid: origin_uuid.to_string(),
+            hops: 0,
+            distortions: 0,
+        };
+        self.next_rumor_id += 1;
+        self.origins.insert(rumor.id, rumor.clone());
+        rumor
+    }
+
+    pub fn origin(&self, rumor_id: u64) -> Option<&Rumor> {
+        self.origins.get(&rumor_id)
+    }
+
+    // every retelling of a rumor in the order it happened
+    pub fn trace(&self, rumor_id: u64) -> Vec<&RumorTransfer> {
+        self.transfers
+            .iter()
+            .filter(|transfer| transfer.rumor_id == rumor_id)
+            .collect()
+    }
+
+    // uuids of every pet that has heard the rumor, its origin first
+    pub fn carriers(&self, rumor_id: u64) -> Vec<String> {
+        let Some(origin) = self.origin(rumor_id) else {
+            return Vec::new();
+        };
+        let mut carriers = vec![origin.origin_uuid.clone()];
+        for transfer in self.trace(rumor_id) {
+            if !carriers.contains(&transfer.to_uuid) {
+                carriers.push(transfer.to_uuid.clone());
+            }
+        }
+        carriers
+    }
+
+    // the chain of pets the rumor went through to reach `uuid`, starting at
+    // its origin, or None if that pet never heard it
+    pub fn path_to(&self, rumor_id: u64, uuid: &str) -> Option<Vec<String>> {
+        let origin = self.origin(rumor_id)?;
+        let transfers = self.trace(rumor_id);
+
+        let mut path = vec![uuid.to_string()];
+        let mut current = uuid;
+        while current != origin.origin_uuid {
+            // the first time a pet heard it is how the rumor reached them
+            let transfer = transfers
+                .iter()
+                .find(|transfer| transfer.to_uuid == current)?;
+            current = transfer.from_uuid.as_str();
+            path.push(current.to_string());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn rumors_about(&self, subject_uuid: &str) -> Vec<&Rumor> {
+        let mut rumors: Vec<&Rumor> = self
+            .origins
+            .values()
+            .filter(|rumor| rumor.subject_uuid == subject_uuid)
+            .collect();
+        rumors.sort_by_key(|rumor| rumor.id);
+        rumors
+    }
+}
+
+pub fn claim_for_emotion(emotion: BasicEmotions) -> &'static str {
+    match emotion {
+        BasicEmotions::Happiness => "is the sweetest pet on the whole farm",
+        BasicEmotions::Excitement => "has some big plans nobody knows about yet",
+        BasicEmotions::Sadness => "has been moping around the pond all week",
+        BasicEmotions::Fear => "is scared of their own shadow",
+        BasicEmotions::Disgust => "never washes behind their ears",
+        BasicEmotions::Hate => "has it out for everybody",
+    }
+}
+
+// the emotion whose valence is closest to `sentiment`
+pub fn emotion_for_sentiment(sentiment: f32) -> BasicEmotions {
+    BasicEmotions::ALL
+        .into_iter()
+        .min_by(|a, b| {
+            (a.valence() - sentiment)
+                .abs()
+                .total_cmp(&(b.valence() - sentiment).abs())
+        })
+        .unwrap_or(BasicEmotions::Happiness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(rumor_id: u64, from_uuid: &str, to_uuid: &str) -> RumorTransfer {
+        RumorTransfer {
+            rumor_id,
+            from_uuid: from_uuid.to_string(),
+            to_uuid: to_uuid.to_string(),
+            claim: String::new(),
+            sentiment: 0.0,
+            hops: 1,
+            distorted: false,
+        }
+    }
+
+    // a makes up a rumor about z and it goes a -> b -> c, with a second
+    // rumor and a repeat telling mixed in
+    fn chain_log() -> (RumorLog, u64) {
+        let mut rumor_log = RumorLog::default();
+        let rumor = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Fear);
+        let other = rumor_log.start_rumor("b", "z", "Zed", BasicEmotions::Happiness);
+        rumor_log.transfers.push(transfer(rumor.id, "a", "b"));
+        rumor_log.transfers.push(transfer(other.id, "b", "d"));
+        rumor_log.transfers.push(transfer(rumor.id, "b", "c"));
+        rumor_log.transfers.push(transfer(rumor.id, "a", "c"));
+        (rumor_log, rumor.id)
+    }
+
+    #[test]
+    fn rumors_get_their_own_ids() {
+        let (rumor_log, rumor_id) = chain_log();
+        let rumor = rumor_log.origin(rumor_id).unwrap();
+        assert_eq!(rumor.origin_uuid, "a");
+        assert_eq!(rumor.claim, claim_for_emotion(BasicEmotions::Fear));
+        assert_eq!(rumor_log.next_rumor_id, 2);
+        assert_eq!(rumor_log.rumors_about("z").len(), 2);
+        assert!(rumor_log.rumors_about("a").is_empty());
+    }
+
+    #[test]
+    fn trace_and_carriers_follow_one_rumor() {
+        let (rumor_log, rumor_id) = chain_log();
+        let hands: Vec<(&str, &str)> = rumor_log
+            .trace(rumor_id)
+            .iter()
+            .map(|transfer| (transfer.from_uuid.as_str(), transfer.to_uuid.as_str()))
+            .collect();
+        assert_eq!(hands, vec![("a", "b"), ("b", "c"), ("a", "c")]);
+        assert_eq!(rumor_log.carriers(rumor_id), vec!["a", "b", "c"]);
+        assert!(rumor_log.carriers(99).is_empty());
+    }
+
+    #[test]
+    fn path_to_goes_through_the_first_telling() {
+        let (rumor_log, rumor_id) = chain_log();
+        assert_eq!(
+            rumor_log.path_to(rumor_id, "c"),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            rumor_log.path_to(rumor_id, "a"),
+            Some(vec!["a".to_string()])
+        );
+        assert_eq!(rumor_log.path_to(rumor_id, "d"), None);
+        assert_eq!(rumor_log.path_to(99, "c"), None);
+    }
+
+    #[test]
+    fn memory_forgets_the_oldest_rumors_past_capacity() {
+        let mut rumor_log = RumorLog::default();
+        let mut rumor_memory = RumorMemory::default();
+        for _ in 0..5 {
+            let rumor = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Sadness);
+            rumor_memory.remember(rumor, 3);
+        }
+        assert_eq!(rumor_memory.rumors.len(), 3);
+        assert!(!rumor_memory.knows(1));
+        assert!(rumor_memory.knows(2));
+        assert!(rumor_memory.knows(4));
+    }
+
+    #[test]
+    fn each_emotion_reads_back_from_its_sentiment() {
+        for emotion in BasicEmotions::ALL {
+            assert_eq!(emotion_for_sentiment(emotion.valence()), emotion);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::spread_rumors_on_conversation;
+
+pub mod components;
+use components::{GossipSettings, RumorLog};
+
+pub struct GossipPlugin;
+
+impl Plugin for GossipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GossipSettings>()
+            .init_resource::<RumorLog>()
+            .add_systems(
+                FixedUpdate,
+                spread_rumors_on_conversation.in_set(FarmSet::Gossip),
+            );
+    }
+}
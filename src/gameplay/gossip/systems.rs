@@ -0,0 +1,380 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::gossip::components::{
+    claim_for_emotion, emotion_for_sentiment, GossipSettings, Rumor, RumorLog, RumorMemory,
+    RumorTransfer,
+};
+use crate::gameplay::player::components::{EtherPet, PlayerData, PlayerUniqueId};
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::SimRng;
+
+// events
+use crate::gameplay::events::ConversationFinishedEvent;
+
+// Passes on the rumor as the teller remembers it. Distorted retellings drift
+// toward how the teller feels about the subject.
+fn retell(
+    rumor: &Rumor,
+    teller_affinity: f32,
+    gossip_settings: &GossipSettings,
+    rng: &mut impl Rng,
+) -> (Rumor, bool) {
+    let mut heard = rumor.clone();
+    heard.hops += 1;
+
+    let distorted = rng.gen_bool(gossip_settings.distortion_chance);
+    if distorted {
+        let shift = rng.gen_range(-0.5..=0.5) + teller_affinity * 0.3;
+        heard.sentiment = (heard.sentiment + shift).clamp(-1.0, 1.0);
+        heard.claim = claim_for_emotion(emotion_for_sentiment(heard.sentiment)).to_string();
+        heard.distortions += 1;
+    }
+
+    (heard, distorted)
+}
+
+// What the listener picks up from the teller, each rumor as it was retold
+// and whether it got twisted along the way
+fn share_rumors(
+    teller_uuid: &str,
+    teller_memory: &RumorMemory,
+    listener_uuid: &str,
+    listener_memory: &RumorMemory,
+    gossip_settings: &GossipSettings,
+    relationship_graph: &RelationshipGraph,
+    rng: &mut impl Rng,
+) -> Vec<(Rumor, bool)> {
+    let mut heard_rumors = Vec::new();
+    for rumor in teller_memory.rumors.iter() {
+        // nobody gossips about a pet to its face
+        if rumor.subject_uuid == listener_uuid
+            || rumor.hops >= gossip_settings.max_hops
+            || listener_memory.knows(rumor.id)
+            || !rng.gen_bool(gossip_settings.share_chance)
+        {
+            continue;
+        }
+
+        // the listener wasn't paying attention
+        if rng.gen_bool(gossip_settings.decay_chance) {
+            continue;
+        }
+
+        let teller_affinity = relationship_graph.affinity(teller_uuid, &rumor.subject_uuid);
+        heard_rumors.push(retell(rumor, teller_affinity, gossip_settings, rng));
+    }
+    heard_rumors
+}
+
+fn hear_rumors(
+    teller_uuid: &str,
+    listener_uuid: &str,
+    listener_memory: &mut RumorMemory,
+    heard_rumors: Vec<(Rumor, bool)>,
+    gossip_settings: &GossipSettings,
+    rumor_log: &mut RumorLog,
+    relationship_graph: &mut RelationshipGraph,
+) {
+    for (heard, distorted) in heard_rumors {
+        let relationship = relationship_graph.get_mut(listener_uuid, &heard.subject_uuid);
+        relationship.affinity = (relationship.affinity
+            + heard.sentiment * gossip_settings.affinity_influence)
+            .clamp(-1.0, 1.0);
+
+        rumor_log.transfers.push(RumorTransfer {
+            rumor_id: heard.id,
+            from_uuid: teller_uuid.to_string(),
+            to_uuid: listener_uuid.to_string(),
+            claim: heard.claim.clone(),
+            sentiment: heard.sentiment,
+            hops: heard.hops,
+            distorted,
+        });
+        listener_memory.remember(heard, gossip_settings.memory_capacity);
+    }
+}
+
+// Only pairs that actually met and finished talking swap rumors
+pub fn spread_rumors_on_conversation(
+    mut conversation_finished_event_reader: EventReader<ConversationFinishedEvent>,
+    gossip_settings: Res<GossipSettings>,
+    mut rumor_log: ResMut<RumorLog>,
+    mut relationship_graph: ResMut<RelationshipGraph>,
+    mut sim_rng: ResMut<SimRng>,
+    mut pets_query: Query<(&PlayerUniqueId, &PlayerData, &mut RumorMemory), With<EtherPet>>,
+) {
+    let rng = sim_rng.as_mut();
+
+    for event in conversation_finished_event_reader.read() {
+        let Ok([(id_a, data_a, mut memory_a), (id_b, data_b, mut memory_b)]) =
+            pets_query.get_many_mut([event.entity_a, event.entity_b])
+        else {
+            continue;
+        };
+
+        // each pet might walk away with something to say about the other
+        if rng.gen_bool(gossip_settings.invent_chance) {
+            let rumor = rumor_log.start_rumor(&id_a.uuid, &id_b.uuid, &data_b.name, data_b.emotion);
+            memory_a.remember(rumor, gossip_settings.memory_capacity);
+        }
+        if rng.gen_bool(gossip_settings.invent_chance) {
+            let rumor = rumor_log.start_rumor(&id_b.uuid, &id_a.uuid, &data_a.name, data_a.emotion);
+            memory_b.remember(rumor, gossip_settings.memory_capacity);
+        }
+
+        // both pets talk about what they knew before the conversation,
+        // so nothing bounces straight back
+        let heard_by_b = share_rumors(
+            &id_a.uuid,
+            &memory_a,
+            &id_b.uuid,
+            &memory_b,
+            &gossip_settings,
+            &relationship_graph,
+            rng,
+        );
+        let heard_by_a = share_rumors(
+            &id_b.uuid,
+            &memory_b,
+            &id_a.uuid,
+            &memory_a,
+            &gossip_settings,
+            &relationship_graph,
+            rng,
+        );
+
+        hear_rumors(
+            &id_a.uuid,
+            &id_b.uuid,
+            &mut memory_b,
+            heard_by_b,
+            &gossip_settings,
+            &mut rumor_log,
+            &mut relationship_graph,
+        );
+        hear_rumors(
+            &id_b.uuid,
+            &id_a.uuid,
+            &mut memory_a,
+            heard_by_a,
+            &gossip_settings,
+            &mut rumor_log,
+            &mut relationship_graph,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::player::components::BasicEmotions;
+
+    // every rumor is passed on and remembered exactly as told
+    fn faithful_settings() -> GossipSettings {
+        GossipSettings {
+            share_chance: 1.0,
+            distortion_chance: 0.0,
+            decay_chance: 0.0,
+            ..default()
+        }
+    }
+
+    fn tell(
+        teller_uuid: &str,
+        teller_memory: &RumorMemory,
+        listener_uuid: &str,
+        listener_memory: &mut RumorMemory,
+        gossip_settings: &GossipSettings,
+        rumor_log: &mut RumorLog,
+        relationship_graph: &mut RelationshipGraph,
+        rng: &mut SimRng,
+    ) {
+        let heard_rumors = share_rumors(
+            teller_uuid,
+            teller_memory,
+            listener_uuid,
+            listener_memory,
+            gossip_settings,
+            relationship_graph,
+            rng,
+        );
+        hear_rumors(
+            teller_uuid,
+            listener_uuid,
+            listener_memory,
+            heard_rumors,
+            gossip_settings,
+            rumor_log,
+            relationship_graph,
+        );
+    }
+
+    #[test]
+    fn rumor_spreads_down_a_chain_of_pets() {
+        let gossip_settings = faithful_settings();
+        let mut rumor_log = RumorLog::default();
+        let mut relationship_graph = RelationshipGraph::default();
+        let mut rng = SimRng::new(7);
+        let mut memory_a = RumorMemory::default();
+        let mut memory_b = RumorMemory::default();
+        let mut memory_c = RumorMemory::default();
+
+        let rumor = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Hate);
+        memory_a.remember(rumor.clone(), 12);
+
+        tell(
+            "a",
+            &memory_a,
+            "b",
+            &mut memory_b,
+            &gossip_settings,
+            &mut rumor_log,
+            &mut relationship_graph,
+            &mut rng,
+        );
+        tell(
+            "b",
+            &memory_b,
+            "c",
+            &mut memory_c,
+            &gossip_settings,
+            &mut rumor_log,
+            &mut relationship_graph,
+            &mut rng,
+        );
+
+        let heard = &memory_c.rumors[0];
+        assert_eq!(heard.id, rumor.id);
+        assert_eq!(heard.hops, 2);
+        assert_eq!(heard.distortions, 0);
+        assert_eq!(heard.claim, rumor.claim);
+        assert_eq!(rumor_log.carriers(rumor.id), vec!["a", "b", "c"]);
+        assert_eq!(
+            rumor_log.path_to(rumor.id, "c"),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        // hearing slander about z soured both listeners on it
+        assert!(relationship_graph.affinity("b", "z") < 0.0);
+        assert!(relationship_graph.affinity("c", "z") < 0.0);
+        assert_eq!(relationship_graph.affinity("a", "z"), 0.0);
+    }
+
+    #[test]
+    fn rumors_are_not_retold_to_their_subject_or_past_max_hops() {
+        let gossip_settings = GossipSettings {
+            max_hops: 1,
+            ..faithful_settings()
+        };
+        let mut rumor_log = RumorLog::default();
+        let relationship_graph = RelationshipGraph::default();
+        let mut rng = SimRng::new(7);
+
+        let mut teller_memory = RumorMemory::default();
+        let about_listener = rumor_log.start_rumor("a", "b", "Bun", BasicEmotions::Fear);
+        let mut worn_out = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Fear);
+        worn_out.hops = 1;
+        let fresh = rumor_log.start_rumor("a", "y", "Yip", BasicEmotions::Fear);
+        let known = rumor_log.start_rumor("a", "x", "Xan", BasicEmotions::Fear);
+        for rumor in [about_listener, worn_out, fresh.clone(), known.clone()] {
+            teller_memory.remember(rumor, 12);
+        }
+        let mut listener_memory = RumorMemory::default();
+        listener_memory.remember(known, 12);
+
+        let heard_rumors = share_rumors(
+            "a",
+            &teller_memory,
+            "b",
+            &listener_memory,
+            &gossip_settings,
+            &relationship_graph,
+            &mut rng,
+        );
+        let heard_ids: Vec<u64> = heard_rumors.iter().map(|(rumor, _)| rumor.id).collect();
+        assert_eq!(heard_ids, vec![fresh.id]);
+    }
+
+    #[test]
+    fn decayed_rumors_are_lost() {
+        let gossip_settings = GossipSettings {
+            decay_chance: 1.0,
+            ..faithful_settings()
+        };
+        let mut rumor_log = RumorLog::default();
+        let mut teller_memory = RumorMemory::default();
+        teller_memory.remember(
+            rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Fear),
+            12,
+        );
+
+        let heard_rumors = share_rumors(
+            "a",
+            &teller_memory,
+            "b",
+            &RumorMemory::default(),
+            &gossip_settings,
+            &RelationshipGraph::default(),
+            &mut SimRng::new(7),
+        );
+        assert!(heard_rumors.is_empty());
+    }
+
+    #[test]
+    fn faithful_retelling_only_adds_a_hop() {
+        let mut rumor_log = RumorLog::default();
+        let rumor = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Sadness);
+
+        let (heard, distorted) = retell(&rumor, 1.0, &faithful_settings(), &mut SimRng::new(7));
+        assert!(!distorted);
+        assert_eq!(heard.hops, 1);
+        assert_eq!(heard.distortions, 0);
+        assert_eq!(heard.sentiment, rumor.sentiment);
+        assert_eq!(heard.claim, rumor.claim);
+    }
+
+    #[test]
+    fn distorted_retelling_changes_the_claim_to_match() {
+        let gossip_settings = GossipSettings {
+            distortion_chance: 1.0,
+            ..faithful_settings()
+        };
+        let mut rumor_log = RumorLog::default();
+        let rumor = rumor_log.start_rumor("a", "z", "Zed", BasicEmotions::Sadness);
+        let mut rng = SimRng::new(7);
+
+        for _ in 0..20 {
+            let (heard, distorted) = retell(&rumor, 1.0, &gossip_settings, &mut rng);
+            assert!(distorted);
+            assert_eq!(heard.distortions, 1);
+            assert!((-1.0..=1.0).contains(&heard.sentiment));
+            assert_eq!(
+                heard.claim,
+                claim_for_emotion(emotion_for_sentiment(heard.sentiment))
+            );
+        }
+    }
+
+    #[test]
+    fn same_seed_spreads_the_same_way() {
+        let gossip_settings = GossipSettings::default();
+        let spread = |seed: u64| {
+            let mut rumor_log = RumorLog::default();
+            let mut teller_memory = RumorMemory::default();
+            for subject in ["w", "x", "y", "z"] {
+                let rumor = rumor_log.start_rumor("a", subject, subject, BasicEmotions::Fear);
+                teller_memory.remember(rumor, 12);
+            }
+            share_rumors(
+                "a",
+                &teller_memory,
+                "b",
+                &RumorMemory::default(),
+                &gossip_settings,
+                &RelationshipGraph::default(),
+                &mut SimRng::new(seed),
+            )
+        };
+        assert_eq!(spread(11), spread(11));
+    }
+}
@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+// Where an entity stood after the last two fixed ticks. Its Transform holds
+// the simulated position while the tick runs and a blend of the two when
+// the frame is drawn.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InterpolatedTranslation {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl InterpolatedTranslation {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::{
+    interpolate_translation, record_simulated_translation, restore_simulated_translation,
+};
+
+pub mod components;
+
+// Pets and cows move in `FixedUpdate`, so frames drawn between two ticks
+// blend their last two simulated positions instead of stuttering
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, restore_simulated_translation)
+            .add_systems(FixedLast, record_simulated_translation)
+            .add_systems(Update, interpolate_translation.in_set(FarmSet::Interpolate));
+    }
+}
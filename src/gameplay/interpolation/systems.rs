@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::interpolation::components::InterpolatedTranslation;
+
+// undoes the blend from the last frame so the tick moves the entity on from
+// where the simulation left it
+pub fn restore_simulated_translation(
+    mut query: Query<(&mut Transform, &mut InterpolatedTranslation)>,
+) {
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = interpolated.current;
+        transform.translation = interpolated.current;
+    }
+}
+
+pub fn record_simulated_translation(mut query: Query<(&Transform, &mut InterpolatedTranslation)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.current = transform.translation;
+    }
+}
+
+pub fn interpolate_translation(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &InterpolatedTranslation)>,
+) {
+    let blend = fixed_time.overstep_fraction();
+    for (mut transform, interpolated) in query.iter_mut() {
+        transform.translation = interpolated.previous.lerp(interpolated.current, blend);
+    }
+}
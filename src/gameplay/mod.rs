@@ -1,37 +1,119 @@
 use crate::prelude::*;
 
-// pub mod audio;
-// pub mod camera;
+pub mod audio;
+pub mod behaviour;
+pub mod camera;
+pub mod clock;
 pub mod components;
+pub mod conversation;
 // pub mod debug;
+pub mod dialogue;
 pub mod events;
-// pub mod npc;
+pub mod gossip;
+pub mod interpolation;
+pub mod mood;
+pub mod needs;
+pub mod npc;
+pub mod pathfinding;
 pub mod player;
+pub mod relationship;
+pub mod rng;
+pub mod save;
+pub mod simulation;
 pub mod spritesheet;
 pub mod state;
 pub mod systems;
 pub mod tile;
-// use audio::AudioSystemPlugin;
-// use camera::CameraPlugin;
-use events::ConversationActionEvent;
-// use npc::NpcPlugin;
+pub mod ui;
+use audio::events::PlaySoundEffectEvent;
+use audio::AudioSystemPlugin;
+use behaviour::BehaviourPlugin;
+use camera::CameraPlugin;
+use clock::ClockPlugin;
+use conversation::ConversationPlugin;
+use dialogue::DialoguePlugin;
+use events::{ConversationActionEvent, ConversationFinishedEvent, ConversationUnreachableEvent};
+use gossip::GossipPlugin;
+use interpolation::InterpolationPlugin;
+use mood::MoodPlugin;
+use needs::NeedsPlugin;
+use npc::NpcPlugin;
 use player::PlayerPlugin;
+use relationship::RelationshipPlugin;
+use rng::RngPlugin;
+use save::SavePlugin;
 use spritesheet::SpriteSheetPlugin;
-// use tile::TileMapPlugin;
+use tile::TileMapPlugin;
+use ui::UiPlugin;
 
+use components::{FarmSet, FarmTuning};
 use systems::exit_game;
 
+// The pets, the map and everything they get up to. Needs no window, so it
+// runs the same under `GamePlayPlugin` and the headless `SimulationPlugin`.
+pub struct FarmPlugin;
+
+impl Plugin for FarmPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ConversationActionEvent>()
+            .add_event::<ConversationFinishedEvent>()
+            .add_event::<ConversationUnreachableEvent>()
+            // sent while animating, played only when there's audio
+            .add_event::<PlaySoundEffectEvent>()
+            .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .init_resource::<FarmTuning>()
+            .configure_sets(
+                Update,
+                (
+                    FarmSet::Load,
+                    FarmSet::Input,
+                    FarmSet::Dialogue,
+                    FarmSet::Save,
+                    FarmSet::Interpolate,
+                )
+                    .chain(),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (
+                    FarmSet::Clock,
+                    FarmSet::Needs,
+                    FarmSet::Behaviour,
+                    FarmSet::Pets,
+                    FarmSet::Cows,
+                    FarmSet::Conversation,
+                    FarmSet::Relationships,
+                    FarmSet::Gossip,
+                    FarmSet::Mood,
+                )
+                    .chain(),
+            )
+            .add_plugins(RngPlugin)
+            .add_plugins(InterpolationPlugin)
+            .add_plugins(PlayerPlugin)
+            .add_plugins(NpcPlugin)
+            .add_plugins(SpriteSheetPlugin)
+            .add_plugins(TileMapPlugin)
+            .add_plugins(ConversationPlugin)
+            .add_plugins(RelationshipPlugin)
+            .add_plugins(GossipPlugin)
+            .add_plugins(MoodPlugin)
+            .add_plugins(NeedsPlugin)
+            .add_plugins(ClockPlugin)
+            .add_plugins(BehaviourPlugin)
+            .add_plugins(SavePlugin);
+    }
+}
+
 pub struct GamePlayPlugin;
 
 impl Plugin for GamePlayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<ConversationActionEvent>()
-            .add_system(exit_game)
-            // .add_plugin(CameraPlugin)
-            .add_plugin(PlayerPlugin)
-        // .add_plugin(NpcPlugin)
-        .add_plugin(SpriteSheetPlugin)
-        // .add_plugin(AudioSystemPlugin)
-        // .add_plugin(TileMapPlugin);
+        app.add_systems(Update, exit_game)
+            .add_plugins(FarmPlugin)
+            .add_plugins(CameraPlugin)
+            .add_plugins(AudioSystemPlugin)
+            .add_plugins(DialoguePlugin)
+            .add_plugins(UiPlugin);
     }
 }
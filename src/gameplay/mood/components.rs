@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+
+// pets whose combined willingness is below this won't stop to chat
+pub const MIN_CHAT_WILLINGNESS: f32 = 0.15;
+
+// How strongly a pet feels each emotion, from 0.0 to 1.0. Levels drift back
+// toward the pet's temperament over time.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mood {
+    // indexed like `BasicEmotions::ALL`
+    pub levels: [f32; 6],
+    // the emotion the pet falls back to, what it was spawned with
+    pub temperament: BasicEmotions,
+    // only changes when another emotion clearly takes over, see `update_dominant`
+    pub dominant: BasicEmotions,
+}
+
+fn emotion_index(emotion: BasicEmotions) -> usize {
+    BasicEmotions::ALL
+        .iter()
+        .position(|other| *other == emotion)
+        .unwrap_or_default()
+}
+
+impl Mood {
+    pub fn new(temperament: BasicEmotions, temperament_level: f32) -> Self {
+        let mut mood = Self {
+            levels: [0.0; 6],
+            temperament,
+            dominant: temperament,
+        };
+        mood.levels[emotion_index(temperament)] = temperament_level;
+        mood
+    }
+
+    pub fn level(&self, emotion: BasicEmotions) -> f32 {
+        self.levels[emotion_index(emotion)]
+    }
+
+    pub fn shift(&mut self, emotion: BasicEmotions, amount: f32) {
+        let level = &mut self.levels[emotion_index(emotion)];
+        *level = (*level + amount).clamp(0.0, 1.0);
+    }
+
+    // eases every level toward the temperament baseline, `blend` is the
+    // fraction of the gap closed this step
+    pub fn decay(&mut self, blend: f32, temperament_level: f32) {
+        for (emotion, level) in BasicEmotions::ALL.iter().zip(self.levels.iter_mut()) {
+            let baseline = if *emotion == self.temperament {
+                temperament_level
+            } else {
+                0.0
+            };
+            *level += (baseline - *level) * blend;
+        }
+    }
+
+    pub fn strongest(&self) -> BasicEmotions {
+        BasicEmotions::ALL
+            .into_iter()
+            .fold(self.dominant, |strongest, emotion| {
+                if self.level(emotion) > self.level(strongest) {
+                    emotion
+                } else {
+                    strongest
+                }
+            })
+    }
+
+    // switches the dominant emotion once another one leads it by `margin`, so
+    // two close emotions don't flicker back and forth. Returns true on a switch
+    pub fn update_dominant(&mut self, margin: f32) -> bool {
+        let strongest = self.strongest();
+        if strongest != self.dominant && self.level(strongest) > self.level(self.dominant) + margin
+        {
+            self.dominant = strongest;
+            return true;
+        }
+        false
+    }
+
+    // how fast the pet wanders compared to its base speed
+    pub fn speed_factor(&self) -> f32 {
+        let intensity = self.level(self.dominant);
+        let full_effect = match self.dominant {
+            BasicEmotions::Excitement => 1.4,
+            BasicEmotions::Happiness => 1.1,
+            BasicEmotions::Fear => 1.25,
+            BasicEmotions::Hate => 1.0,
+            BasicEmotions::Disgust => 0.9,
+            BasicEmotions::Sadness => 0.6,
+        };
+        1.0 + (full_effect - 1.0) * intensity
+    }
+
+    // from 0.0 (wants to be left alone) to 1.0 (talks to anyone)
+    pub fn chat_willingness(&self) -> f32 {
+        let intensity = self.level(self.dominant);
+        let full_effect = match self.dominant {
+            BasicEmotions::Happiness | BasicEmotions::Excitement => 1.0,
+            BasicEmotions::Disgust => 0.5,
+            BasicEmotions::Sadness => 0.4,
+            BasicEmotions::Fear => 0.3,
+            BasicEmotions::Hate => 0.2,
+        };
+        1.0 + (full_effect - 1.0) * intensity
+    }
+
+    pub fn wants_to_chat_with(&self, other: &Mood) -> bool {
+        self.chat_willingness() * other.chat_willingness() >= MIN_CHAT_WILLINGNESS
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct MoodSettings {
+    // level of the temperament emotion when nothing is going on
+    pub temperament_level: f32,
+    // fraction of the gap to the baseline closed per second
+    pub decay_rate: f32,
+    // how far ahead another emotion has to be to take over
+    pub dominance_margin: f32,
+    // shift toward the emote a pet walked away from a conversation with
+    pub conversation_shift: f32,
+    // happiness per second while eating
+    pub eating_shift: f32,
+    // disgust per second near a pet it avoids, hate near a rival
+    pub disliked_pet_shift: f32,
+    pub disliked_pet_distance: f32,
+}
+
+impl Default for MoodSettings {
+    fn default() -> Self {
+        Self {
+            temperament_level: 0.4,
+            decay_rate: 0.05,
+            dominance_margin: 0.1,
+            conversation_shift: 0.35,
+            eating_shift: 0.06,
+            disliked_pet_shift: 0.08,
+            disliked_pet_distance: TILE_SIZE * 4.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+    }
+
+    // a pet feeling nothing but `emotion`, at `level`
+    fn feeling(emotion: BasicEmotions, level: f32) -> Mood {
+        let mut mood = Mood::new(emotion, level);
+        mood.update_dominant(0.0);
+        mood
+    }
+
+    #[test]
+    fn decay_eases_levels_toward_the_temperament() {
+        let mut mood = Mood::new(BasicEmotions::Happiness, 0.4);
+        mood.shift(BasicEmotions::Fear, 0.8);
+        mood.shift(BasicEmotions::Happiness, -1.0);
+
+        mood.decay(0.5, 0.4);
+        assert_close(mood.level(BasicEmotions::Fear), 0.4);
+        assert_close(mood.level(BasicEmotions::Happiness), 0.2);
+
+        mood.decay(1.0, 0.4);
+        assert_close(mood.level(BasicEmotions::Fear), 0.0);
+        assert_close(mood.level(BasicEmotions::Happiness), 0.4);
+    }
+
+    #[test]
+    fn dominant_emotion_only_switches_past_the_margin() {
+        let mut mood = Mood::new(BasicEmotions::Happiness, 0.4);
+
+        mood.shift(BasicEmotions::Sadness, 0.45);
+        assert_eq!(mood.strongest(), BasicEmotions::Sadness);
+        assert!(!mood.update_dominant(0.1));
+        assert_eq!(mood.dominant, BasicEmotions::Happiness);
+
+        mood.shift(BasicEmotions::Sadness, 0.1);
+        assert!(mood.update_dominant(0.1));
+        assert_eq!(mood.dominant, BasicEmotions::Sadness);
+
+        // and doesn't flick straight back once happiness edges ahead
+        mood.shift(BasicEmotions::Happiness, 0.2);
+        assert!(!mood.update_dominant(0.1));
+        assert_eq!(mood.dominant, BasicEmotions::Sadness);
+    }
+
+    #[test]
+    fn chat_willingness_scales_with_the_dominant_emotion() {
+        assert_close(
+            feeling(BasicEmotions::Happiness, 1.0).chat_willingness(),
+            1.0,
+        );
+        assert_close(feeling(BasicEmotions::Hate, 1.0).chat_willingness(), 0.2);
+        assert_close(feeling(BasicEmotions::Fear, 0.5).chat_willingness(), 0.65);
+        assert_close(feeling(BasicEmotions::Sadness, 0.0).chat_willingness(), 1.0);
+    }
+
+    #[test]
+    fn pets_below_the_willingness_threshold_do_not_chat() {
+        let happy = feeling(BasicEmotions::Happiness, 1.0);
+        let sad = feeling(BasicEmotions::Sadness, 1.0);
+        let hateful = feeling(BasicEmotions::Hate, 1.0);
+        let scared = feeling(BasicEmotions::Fear, 1.0);
+
+        // 0.2 and 0.16 clear MIN_CHAT_WILLINGNESS, 0.06 and 0.04 don't
+        assert!(hateful.wants_to_chat_with(&happy));
+        assert!(sad.wants_to_chat_with(&sad));
+        assert!(!hateful.wants_to_chat_with(&scared));
+        assert!(!hateful.wants_to_chat_with(&hateful));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::{
+    decay_moods, shift_moods_from_conversations, shift_moods_near_disliked_pets,
+    shift_moods_while_eating, update_dominant_emotions,
+};
+
+pub mod components;
+use components::MoodSettings;
+
+pub struct MoodPlugin;
+
+impl Plugin for MoodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoodSettings>().add_systems(
+            FixedUpdate,
+            (
+                decay_moods,
+                shift_moods_from_conversations,
+                shift_moods_while_eating,
+                shift_moods_near_disliked_pets,
+                update_dominant_emotions,
+            )
+                .chain()
+                .in_set(FarmSet::Mood),
+        );
+    }
+}
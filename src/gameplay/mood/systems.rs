@@ -0,0 +1,108 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::{ActiveBehaviour, BehaviourKind};
+use crate::gameplay::mood::components::{Mood, MoodSettings};
+use crate::gameplay::player::components::{BasicEmotions, EtherPet, PlayerData, PlayerUniqueId};
+use crate::gameplay::relationship::components::RelationshipGraph;
+
+// events
+use crate::gameplay::player::events::EmoteEvent;
+use crate::http_request::events::ConversationHistoryEvent;
+
+pub fn decay_moods(
+    time: Res<Time>,
+    mood_settings: Res<MoodSettings>,
+    mut mood_query: Query<&mut Mood>,
+) {
+    // frame rate independent exponential decay
+    let blend = 1.0 - (-mood_settings.decay_rate * time.delta_seconds()).exp();
+    for mut mood in mood_query.iter_mut() {
+        mood.decay(blend, mood_settings.temperament_level);
+    }
+}
+
+// each pet leans toward the emote it walked away from the conversation with
+pub fn shift_moods_from_conversations(
+    mut conversation_history_event_reader: EventReader<ConversationHistoryEvent>,
+    mood_settings: Res<MoodSettings>,
+    mut mood_query: Query<(&PlayerUniqueId, &mut Mood), With<EtherPet>>,
+) {
+    for event in conversation_history_event_reader.read() {
+        for emote in event.response.emotes.iter() {
+            let Some(emotion) = BasicEmotions::parse(&emote.emote) else {
+                continue;
+            };
+            for (unique_id, mut mood) in mood_query.iter_mut() {
+                if unique_id.uuid == emote.entity_uuid {
+                    mood.shift(emotion, mood_settings.conversation_shift);
+                }
+            }
+        }
+    }
+}
+
+pub fn shift_moods_while_eating(
+    time: Res<Time>,
+    mood_settings: Res<MoodSettings>,
+    mut mood_query: Query<(&ActiveBehaviour, &mut Mood), With<EtherPet>>,
+) {
+    for (active_behaviour, mut mood) in mood_query.iter_mut() {
+        if active_behaviour.is_occupied() && active_behaviour.kind == BehaviourKind::Eat {
+            mood.shift(
+                BasicEmotions::Happiness,
+                mood_settings.eating_shift * time.delta_seconds(),
+            );
+        }
+    }
+}
+
+// pets sour when they're stuck near someone they dislike
+pub fn shift_moods_near_disliked_pets(
+    time: Res<Time>,
+    mood_settings: Res<MoodSettings>,
+    relationship_graph: Res<RelationshipGraph>,
+    mut mood_query: Query<(&PlayerUniqueId, &Transform, &mut Mood), With<EtherPet>>,
+) {
+    let pet_positions: Vec<(String, Vec2)> = mood_query
+        .iter()
+        .map(|(unique_id, transform, _)| (unique_id.uuid.clone(), transform.translation.truncate()))
+        .collect();
+
+    let shift = mood_settings.disliked_pet_shift * time.delta_seconds();
+    for (unique_id, transform, mut mood) in mood_query.iter_mut() {
+        let position = transform.translation.truncate();
+        for (other_uuid, other_position) in pet_positions.iter() {
+            if *other_uuid == unique_id.uuid
+                || position.distance(*other_position) > mood_settings.disliked_pet_distance
+            {
+                continue;
+            }
+            if relationship_graph.are_rivals(&unique_id.uuid, other_uuid) {
+                mood.shift(BasicEmotions::Hate, shift);
+            } else if relationship_graph.avoids(&unique_id.uuid, other_uuid) {
+                mood.shift(BasicEmotions::Disgust, shift);
+            }
+        }
+    }
+}
+
+// Keeps `PlayerData.emotion` in step with the mood, so everything reading it
+// (conversations, gossip, relationships) sees how the pet feels right now.
+// A pet emotes whenever its dominant emotion changes
+pub fn update_dominant_emotions(
+    mood_settings: Res<MoodSettings>,
+    mut mood_query: Query<(&PlayerUniqueId, &mut Mood, &mut PlayerData), With<EtherPet>>,
+    mut emote_event_writer: EventWriter<EmoteEvent>,
+) {
+    for (unique_id, mut mood, mut player_data) in mood_query.iter_mut() {
+        if !mood.update_dominant(mood_settings.dominance_margin) {
+            continue;
+        }
+        player_data.emotion = mood.dominant;
+        emote_event_writer.send(EmoteEvent {
+            entity_uuid: unique_id.uuid.clone(),
+            emote: mood.dominant.as_str().to_string(),
+        });
+    }
+}
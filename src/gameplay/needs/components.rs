@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+// `landmark` values of the map layers pets go to for their needs
+pub const FOOD_LANDMARK: &str = "food";
+pub const BED_LANDMARK: &str = "bed";
+
+// Every need goes from 0.0 (satisfied) to 1.0 (desperate)
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Needs {
+    pub hunger: f32,
+    // 1.0 fully rested, 0.0 exhausted
+    pub energy: f32,
+    pub social: f32,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self {
+            hunger: 0.2,
+            energy: 0.9,
+            social: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Need {
+    Hunger,
+    Energy,
+    Social,
+}
+
+impl Needs {
+    pub fn urgency(&self, need: Need) -> f32 {
+        match need {
+            Need::Hunger => self.hunger,
+            Need::Energy => 1.0 - self.energy,
+            Need::Social => self.social,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct NeedsSettings {
+    // per second
+    pub hunger_rate: f32,
+    pub energy_drain: f32,
+    pub social_rate: f32,
+    pub eat_rate: f32,
+    pub sleep_rate: f32,
+    pub rest_rate: f32,
+    pub chat_rate: f32,
+    // urgency at which the pet is done eating or sleeping
+    pub satisfied_threshold: f32,
+}
+
+impl Default for NeedsSettings {
+    fn default() -> Self {
+        Self {
+            hunger_rate: 0.008,
+            energy_drain: 0.005,
+            social_rate: 0.01,
+            eat_rate: 0.12,
+            sleep_rate: 0.06,
+            rest_rate: 0.025,
+            chat_rate: 0.15,
+            satisfied_threshold: 0.1,
+        }
+    }
+}
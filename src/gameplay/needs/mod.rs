@@ -0,0 +1,18 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::tick_needs;
+
+pub mod components;
+use components::NeedsSettings;
+
+pub struct NeedsPlugin;
+
+impl Plugin for NeedsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NeedsSettings>()
+            .add_systems(FixedUpdate, tick_needs.in_set(FarmSet::Needs));
+    }
+}
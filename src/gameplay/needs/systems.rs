@@ -0,0 +1,44 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::{ActiveBehaviour, BehaviourKind};
+use crate::gameplay::components::{EntityActionState, GenericActions, InteractionTimers};
+use crate::gameplay::needs::components::{Needs, NeedsSettings};
+use crate::gameplay::player::components::EtherPet;
+
+pub fn tick_needs(
+    time: Res<Time>,
+    needs_settings: Res<NeedsSettings>,
+    mut needs_query: Query<
+        (
+            &mut Needs,
+            &ActiveBehaviour,
+            &EntityActionState,
+            &InteractionTimers,
+        ),
+        With<EtherPet>,
+    >,
+) {
+    let delta_seconds = time.delta_seconds();
+    for (mut needs, active_behaviour, action_state, interaction_timers) in needs_query.iter_mut() {
+        needs.hunger += needs_settings.hunger_rate * delta_seconds;
+        if active_behaviour.is_occupied() && active_behaviour.kind == BehaviourKind::Eat {
+            needs.hunger -= needs_settings.eat_rate * delta_seconds;
+        }
+
+        needs.energy += match action_state.action {
+            GenericActions::Sleeping => needs_settings.sleep_rate,
+            GenericActions::Resting => needs_settings.rest_rate,
+            _ => -needs_settings.energy_drain,
+        } * delta_seconds;
+
+        needs.social += needs_settings.social_rate * delta_seconds;
+        if interaction_timers.prox_chat_active {
+            needs.social -= needs_settings.chat_rate * delta_seconds;
+        }
+
+        needs.hunger = needs.hunger.clamp(0.0, 1.0);
+        needs.energy = needs.energy.clamp(0.0, 1.0);
+        needs.social = needs.social.clamp(0.0, 1.0);
+    }
+}
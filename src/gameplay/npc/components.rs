@@ -0,0 +1,88 @@
+use crate::prelude::*;
+
+// EtherPets the player isn't controlling
+#[derive(Component, Debug)]
+pub struct NpcEntity;
+
+#[derive(Component, Debug)]
+pub struct Cow;
+
+// rows of the purple cow spritesheet, every row is 8 frames wide
+pub const COW_SHEET_COLUMNS: usize = 8;
+pub const COW_IDLE_ROW: usize = 0;
+pub const COW_WALK_ROW: usize = 1;
+pub const COW_EAT_ROW: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CowAction {
+    Idle,
+    Walk,
+    Graze,
+}
+
+impl CowAction {
+    pub fn animation_row(&self) -> usize {
+        match self {
+            CowAction::Idle => COW_IDLE_ROW,
+            CowAction::Walk => COW_WALK_ROW,
+            CowAction::Graze => COW_EAT_ROW,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct CowBehaviour {
+    pub action: CowAction,
+    // the cow picks a new action when this runs out
+    pub action_timer: Timer,
+    pub direction: Vec2,
+}
+
+#[derive(Component, Debug)]
+pub struct CowAnimation {
+    pub frame_timer: Timer,
+    pub current_frame: usize,
+    pub facing_right: bool,
+}
+
+// cows spawned close together stick together
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CowHerd(pub usize);
+
+#[derive(Resource, Debug, Clone)]
+pub struct CowHerdSettings {
+    // spawn points closer than this start out in the same herd
+    pub herd_radius: f32,
+    // cows further than this from the herd head back to it
+    pub stray_distance: f32,
+    pub cohesion_weight: f32,
+    // cows closer than this shuffle apart
+    pub separation_distance: f32,
+    pub separation_weight: f32,
+    // tiles per second
+    pub walk_speed: f32,
+    pub idle_seconds: (f32, f32),
+    pub walk_seconds: (f32, f32),
+    pub graze_seconds: (f32, f32),
+    // chance of grazing rather than idling or walking when picking an action
+    pub graze_chance: f64,
+    pub walk_chance: f64,
+}
+
+impl Default for CowHerdSettings {
+    fn default() -> Self {
+        Self {
+            herd_radius: TILE_SIZE * 16.0,
+            stray_distance: TILE_SIZE * 6.0,
+            cohesion_weight: 0.6,
+            separation_distance: TILE_SIZE * 1.5,
+            separation_weight: 1.2,
+            walk_speed: 1.0,
+            idle_seconds: (2.0, 5.0),
+            walk_seconds: (1.5, 4.0),
+            graze_seconds: (4.0, 9.0),
+            graze_chance: 0.45,
+            walk_chance: 0.3,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::spritesheet::systems::spritesheets_loaded;
+use super::tile::collision::CollisionGrid;
+use super::tile::components::LoadedMap;
+
+mod systems;
+use systems::{cow_animation_system, cow_behaviour_system, cow_spawner};
+
+pub mod components;
+use components::CowHerdSettings;
+
+pub struct NpcPlugin;
+
+impl Plugin for NpcPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CowHerdSettings>()
+            .add_systems(
+                Update,
+                cow_spawner
+                    .run_if(
+                        resource_exists::<LoadedMap>
+                            .and_then(spritesheets_loaded)
+                            .and_then(run_once()),
+                    )
+                    .in_set(FarmSet::Load),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    cow_behaviour_system.run_if(resource_exists::<CollisionGrid>),
+                    cow_animation_system,
+                )
+                    .chain()
+                    .in_set(FarmSet::Cows),
+            );
+    }
+}
@@ -0,0 +1,210 @@
+use rand::Rng;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::components::BasicEntityCollider;
+use crate::gameplay::interpolation::components::InterpolatedTranslation;
+use crate::gameplay::npc::components::{
+    Cow, CowAction, CowAnimation, CowBehaviour, CowHerd, CowHerdSettings, COW_SHEET_COLUMNS,
+};
+use crate::gameplay::rng::components::{EntityRng, SimRng};
+use crate::gameplay::spritesheet::components::{SpriteSheets, PURPLE_COW_SHEET};
+use crate::gameplay::tile::collision::{entity_footprint, CollisionGrid};
+use crate::gameplay::tile::components::LoadedMap;
+
+// helpers
+use crate::gameplay::spritesheet::systems::spawn_cow_spritesheet_sprite;
+
+// Greedily groups spawn points, each point joins the first herd that has a
+// cow within `herd_radius` of it
+fn assign_herds(spawn_points: &[Vec3], herd_radius: f32) -> Vec<usize> {
+    let mut herds: Vec<usize> = Vec::with_capacity(spawn_points.len());
+    let mut herd_count = 0;
+
+    for (index, spawn_point) in spawn_points.iter().enumerate() {
+        let herd = (0..index)
+            .find(|&other| spawn_points[other].distance(*spawn_point) <= herd_radius)
+            .map(|other| herds[other])
+            .unwrap_or_else(|| {
+                herd_count += 1;
+                herd_count - 1
+            });
+        herds.push(herd);
+    }
+    herds
+}
+
+fn random_seconds(range: (f32, f32), rng: &mut impl Rng) -> f32 {
+    rng.gen_range(range.0..=range.1)
+}
+
+pub fn cow_spawner(
+    mut commands: Commands,
+    sprite_sheets: Res<SpriteSheets>,
+    loaded_map: Res<LoadedMap>,
+    herd_settings: Res<CowHerdSettings>,
+    sim_rng: Res<SimRng>,
+) {
+    let Some(purple_cow_spritesheet) = sprite_sheets.get(PURPLE_COW_SHEET) else {
+        error!("No {:?} spritesheet in the manifest", PURPLE_COW_SHEET);
+        return;
+    };
+
+    let spawn_points = loaded_map.spawn_points("cow_spawn");
+    if spawn_points.is_empty() {
+        warn!("Map has no cow spawn points");
+    }
+
+    let herds = assign_herds(spawn_points, herd_settings.herd_radius);
+
+    for (index, (spawn_point, herd)) in spawn_points.iter().zip(herds).enumerate() {
+        // spawn points come in the same order every run
+        let mut rng = sim_rng.stream(&format!("cow {}", index));
+        let cow_entity =
+            spawn_cow_spritesheet_sprite(&mut commands, purple_cow_spritesheet, 0, *spawn_point);
+
+        commands
+            .entity(cow_entity)
+            .insert(Name::new("Purple Cow"))
+            .insert(InterpolatedTranslation::new(*spawn_point))
+            .insert(Cow)
+            .insert(CowHerd(herd))
+            .insert(BasicEntityCollider {
+                width: 16.0,
+                height: 16.0,
+                collided: false,
+            })
+            // stagger the herd so they don't all move in lockstep
+            .insert(CowBehaviour {
+                action: CowAction::Graze,
+                action_timer: Timer::from_seconds(
+                    random_seconds(herd_settings.graze_seconds, &mut rng),
+                    TimerMode::Once,
+                ),
+                direction: Vec2::ZERO,
+            })
+            .insert(CowAnimation {
+                frame_timer: Timer::from_seconds(0.15, TimerMode::Repeating),
+                current_frame: rng.gen_range(0..COW_SHEET_COLUMNS),
+                facing_right: rng.gen_bool(0.5),
+            })
+            .insert(rng);
+    }
+}
+
+pub fn cow_behaviour_system(
+    time: Res<Time>,
+    herd_settings: Res<CowHerdSettings>,
+    collision_grid: Res<CollisionGrid>,
+    mut cow_query: Query<
+        (
+            &mut Transform,
+            &mut CowBehaviour,
+            &CowHerd,
+            &BasicEntityCollider,
+            &mut EntityRng,
+        ),
+        With<Cow>,
+    >,
+) {
+    let cow_positions: Vec<(CowHerd, Vec2)> = cow_query
+        .iter()
+        .map(|(transform, _, herd, _, _)| (*herd, transform.translation.truncate()))
+        .collect();
+
+    let mut herd_totals: HashMap<CowHerd, (Vec2, f32)> = HashMap::new();
+    for (herd, position) in cow_positions.iter() {
+        let total = herd_totals.entry(*herd).or_insert((Vec2::ZERO, 0.0));
+        total.0 += *position;
+        total.1 += 1.0;
+    }
+
+    for (mut transform, mut behaviour, herd, collider, mut rng) in cow_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let herd_center = herd_totals
+            .get(herd)
+            .map(|(sum, count)| *sum / *count)
+            .unwrap_or(position);
+
+        behaviour.action_timer.tick(time.delta());
+        if behaviour.action_timer.finished() {
+            let strayed = position.distance(herd_center) > herd_settings.stray_distance;
+            let roll = rng.gen_range(0.0..1.0);
+
+            let (action, seconds) = if strayed || roll < herd_settings.walk_chance {
+                (CowAction::Walk, herd_settings.walk_seconds)
+            } else if roll < herd_settings.walk_chance + herd_settings.graze_chance {
+                (CowAction::Graze, herd_settings.graze_seconds)
+            } else {
+                (CowAction::Idle, herd_settings.idle_seconds)
+            };
+
+            if action == CowAction::Walk {
+                // wander, drift back toward the herd and keep a little personal space
+                let wander = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+                let cohesion = (herd_center - position).normalize_or_zero()
+                    * herd_settings.cohesion_weight
+                    * if strayed { 2.0 } else { 1.0 };
+                let separation: Vec2 = cow_positions
+                    .iter()
+                    .map(|(_, other)| position - *other)
+                    .filter(|offset| {
+                        let distance = offset.length();
+                        distance > 0.0 && distance < herd_settings.separation_distance
+                    })
+                    .map(|offset| offset.normalize() * herd_settings.separation_weight)
+                    .sum();
+                behaviour.direction = (wander + cohesion + separation).normalize_or_zero();
+            }
+
+            behaviour.action = action;
+            behaviour.action_timer =
+                Timer::from_seconds(random_seconds(seconds, rng.as_mut()), TimerMode::Once);
+        }
+
+        if behaviour.action != CowAction::Walk {
+            continue;
+        }
+
+        let delta =
+            behaviour.direction * herd_settings.walk_speed * TILE_SIZE * time.delta_seconds();
+        let swept = collision_grid.sweep_aabb(position, entity_footprint(collider), delta);
+        if swept == position && delta != Vec2::ZERO {
+            // walked into a fence, stand around until the next action
+            behaviour.action = CowAction::Idle;
+            continue;
+        }
+        transform.translation.x = swept.x;
+        transform.translation.y = swept.y;
+    }
+}
+
+pub fn cow_animation_system(
+    time: Res<Time>,
+    mut cow_query: Query<
+        (
+            &CowBehaviour,
+            &mut CowAnimation,
+            &mut TextureAtlas,
+            &mut Sprite,
+        ),
+        With<Cow>,
+    >,
+) {
+    for (behaviour, mut animation, mut atlas, mut sprite) in cow_query.iter_mut() {
+        if behaviour.action == CowAction::Walk && behaviour.direction.x != 0.0 {
+            animation.facing_right = behaviour.direction.x > 0.0;
+        }
+
+        animation.frame_timer.tick(time.delta());
+        if animation.frame_timer.just_finished() {
+            animation.current_frame = (animation.current_frame + 1) % COW_SHEET_COLUMNS;
+        }
+
+        atlas.index =
+            behaviour.action.animation_row() * COW_SHEET_COLUMNS + animation.current_frame;
+        // the sheet is drawn facing left
+        sprite.flip_x = animation.facing_right;
+    }
+}
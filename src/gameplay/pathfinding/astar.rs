@@ -0,0 +1,306 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::prelude::*;
+
+use crate::gameplay::tile::collision::CollisionGrid;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+// Tiles a pet will detour round fences and trees to reach whoever it's
+// chatting with, past the tiles between them. Further than that and the pair
+// walks on by.
+const CONVERSATION_DETOUR_TILES: i32 = 3;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+// Tile offsets next to a pet, in tile rows (y grows downwards)
+pub const ABOVE: IVec2 = IVec2::new(0, -1);
+pub const BELOW: IVec2 = IVec2::new(0, 1);
+pub const LEFT: IVec2 = IVec2::new(-1, 0);
+pub const RIGHT: IVec2 = IVec2::new(1, 0);
+
+#[derive(Debug, PartialEq, Eq)]
+struct OpenTile {
+    estimated_cost: u32,
+    tile: IVec2,
+}
+
+// BinaryHeap is a max heap, so the cheapest tile has to compare as the greatest
+impl Ord for OpenTile {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimated_cost
+            .cmp(&self.estimated_cost)
+            .then_with(|| (self.tile.x, self.tile.y).cmp(&(other.tile.x, other.tile.y)))
+    }
+}
+
+impl PartialOrd for OpenTile {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// octile distance, exact for 8-way movement without obstacles
+fn heuristic(from: IVec2, to: IVec2) -> u32 {
+    let delta = (to - from).abs();
+    let straight = delta.x.max(delta.y) - delta.x.min(delta.y);
+    let diagonal = delta.x.min(delta.y);
+    straight as u32 * STRAIGHT_COST + diagonal as u32 * DIAGONAL_COST
+}
+
+// A* over the collision grid with 8-way movement. Diagonal steps are only
+// taken when both side tiles are free so pets never clip a fence corner.
+// The start tile is allowed to be solid, a pet nudged into a wall can still
+// walk out. Returns the tiles to walk through, excluding `start`.
+pub fn find_path(grid: &CollisionGrid, start: IVec2, goal: IVec2) -> Option<Vec<IVec2>> {
+    find_path_within(grid, start, goal, i32::MAX)
+}
+
+// `find_path` that never looks further than `radius` tiles from `start` in
+// any direction, so an unreachable goal gives up after a few dozen tiles
+// instead of flooding the whole map.
+pub fn find_path_within(
+    grid: &CollisionGrid,
+    start: IVec2,
+    goal: IVec2,
+    radius: i32,
+) -> Option<Vec<IVec2>> {
+    if grid.is_solid(goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(Vec::new());
+    }
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut cost_so_far: HashMap<IVec2, u32> = HashMap::new();
+
+    cost_so_far.insert(start, 0);
+    open.push(OpenTile {
+        estimated_cost: heuristic(start, goal),
+        tile: start,
+    });
+
+    while let Some(OpenTile { tile, .. }) = open.pop() {
+        if tile == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(previous) = came_from.get(&current) {
+                if *previous == start {
+                    break;
+                }
+                path.push(*previous);
+                current = *previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let current_cost = cost_so_far[&tile];
+        for offset in NEIGHBOURS {
+            let next = tile + offset;
+            if grid.is_solid(next) || (next - start).abs().max_element() > radius {
+                continue;
+            }
+
+            let diagonal = offset.x != 0 && offset.y != 0;
+            if diagonal
+                && (grid.is_solid(tile + IVec2::new(offset.x, 0))
+                    || grid.is_solid(tile + IVec2::new(0, offset.y)))
+            {
+                continue;
+            }
+
+            let next_cost = current_cost
+                + if diagonal {
+                    DIAGONAL_COST
+                } else {
+                    STRAIGHT_COST
+                };
+            if cost_so_far
+                .get(&next)
+                .is_some_and(|&known_cost| known_cost <= next_cost)
+            {
+                continue;
+            }
+            cost_so_far.insert(next, next_cost);
+            came_from.insert(next, tile);
+            open.push(OpenTile {
+                estimated_cost: next_cost + heuristic(next, goal),
+                tile: next,
+            });
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone)]
+pub struct ConversationPath {
+    // which side of the partner the spot is on, one of ABOVE, BELOW, LEFT, RIGHT
+    pub side: IVec2,
+    // world positions of the tiles to walk through, the last one is the spot
+    pub waypoints: Vec<Vec2>,
+}
+
+// Finds a free tile next to `partner` that can be walked to from `from`.
+// `preferred_side` is tried first, then the remaining sides closest to
+// `from`. The final waypoint is pulled in to `distance` from the partner
+// when that spot is free. None means the partner is walled in or out of reach,
+// which includes a detour of more than a few tiles.
+pub fn find_conversation_path(
+    grid: &CollisionGrid,
+    from: Vec2,
+    partner: Vec2,
+    preferred_side: Option<IVec2>,
+    distance: f32,
+) -> Option<ConversationPath> {
+    let start = grid.world_to_tile(from);
+    let partner_tile = grid.world_to_tile(partner);
+    // the partner's tile, the side next to it and the detour
+    let radius = (partner_tile - start).abs().max_element() + 1 + CONVERSATION_DETOUR_TILES;
+
+    let mut sides: Vec<IVec2> = [ABOVE, BELOW, LEFT, RIGHT]
+        .into_iter()
+        .filter(|side| Some(*side) != preferred_side)
+        .collect();
+    sides.sort_by_key(|side| heuristic(start, partner_tile + *side));
+    if let Some(side) = preferred_side {
+        sides.insert(0, side);
+    }
+
+    sides.into_iter().find_map(|side| {
+        let path = find_path_within(grid, start, partner_tile + side, radius)?;
+        let mut waypoints: Vec<Vec2> = path
+            .into_iter()
+            .map(|tile| grid.tile_to_world(tile))
+            .collect();
+
+        // tile rows grow downwards, world y grows upwards
+        let spot = partner + Vec2::new(side.x as f32, -side.y as f32) * distance;
+        if !grid.is_blocked(spot) {
+            match waypoints.last_mut() {
+                Some(last) => *last = spot,
+                None => waypoints.push(spot),
+            }
+        }
+
+        Some(ConversationPath { side, waypoints })
+    })
+}
+
+// Path to the closest reachable tile out of `targets`, trying at most
+// `max_attempts` of them in order of distance. Solid targets (a tree trunk,
+// a table) are swapped for the free tiles right next to them. Returns the
+// world positions to walk through.
+pub fn find_path_to_nearest(
+    grid: &CollisionGrid,
+    from: Vec2,
+    targets: &[IVec2],
+    max_attempts: usize,
+) -> Option<Vec<Vec2>> {
+    let start = grid.world_to_tile(from);
+
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<IVec2> = targets
+        .iter()
+        .flat_map(|target| {
+            if grid.is_solid(*target) {
+                [ABOVE, BELOW, LEFT, RIGHT]
+                    .into_iter()
+                    .map(|side| *target + side)
+                    .filter(|tile| !grid.is_solid(*tile))
+                    .collect()
+            } else {
+                vec![*target]
+            }
+        })
+        .filter(|tile| seen.insert(*tile))
+        .collect();
+    candidates.sort_by_key(|tile| heuristic(start, *tile));
+
+    candidates
+        .into_iter()
+        .take(max_attempts)
+        .find_map(|goal| find_path(grid, start, goal))
+        .map(|path| {
+            path.into_iter()
+                .map(|tile| grid.tile_to_world(tile))
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::components::FarmTuning;
+
+    // 20x20 tiles with a wall down column 10, open only at row `gap`
+    fn walled_grid(gap: usize) -> CollisionGrid {
+        let mut grid = CollisionGrid::new(20, 20, Vec2::splat(TILE_SIZE));
+        for y in 0..20 {
+            grid.set_solid(10, y, y != gap);
+        }
+        grid
+    }
+
+    #[test]
+    fn bounded_search_gives_up_on_a_long_detour() {
+        let grid = walled_grid(19);
+        let (start, goal) = (IVec2::new(9, 5), IVec2::new(11, 5));
+
+        let path = find_path(&grid, start, goal).unwrap();
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.iter().any(|tile| tile.y == 19));
+
+        assert!(find_path_within(&grid, start, goal, 6).is_none());
+        assert!(find_conversation_path(
+            &grid,
+            grid.tile_to_world(start),
+            grid.tile_to_world(goal),
+            None,
+            FarmTuning::default().convo_distance,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn bounded_search_takes_a_short_detour() {
+        let grid = walled_grid(7);
+        let start = IVec2::new(9, 5);
+        let partner = IVec2::new(11, 5);
+
+        let conversation_path = find_conversation_path(
+            &grid,
+            grid.tile_to_world(start),
+            grid.tile_to_world(partner),
+            Some(LEFT),
+            FarmTuning::default().convo_distance,
+        )
+        .unwrap();
+        // the left of the partner is the wall, so it goes round to another side
+        assert_ne!(conversation_path.side, LEFT);
+        assert!(!conversation_path.waypoints.is_empty());
+    }
+
+    #[test]
+    fn bounded_search_stays_inside_its_radius() {
+        let grid = CollisionGrid::new(20, 20, Vec2::splat(TILE_SIZE));
+        let start = IVec2::new(2, 2);
+        assert!(find_path_within(&grid, start, IVec2::new(5, 5), 3).is_some());
+        assert!(find_path_within(&grid, start, IVec2::new(6, 2), 3).is_none());
+    }
+}
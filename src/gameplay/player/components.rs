@@ -1,14 +1,22 @@
 use crate::prelude::*;
+#[cfg(feature = "inspector")]
 use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
 
-#[derive(Component, Reflect, Resource, Default, InspectorOptions, Debug)]
-#[reflect(Resource, InspectorOptions)]
+use crate::data::player_data::get_player_data;
+use crate::gameplay::pathfinding::astar::{ABOVE, BELOW, LEFT, RIGHT};
+
+#[derive(Component, Reflect, Resource, Default, Debug)]
+#[cfg_attr(feature = "inspector", derive(InspectorOptions))]
+#[cfg_attr(feature = "inspector", reflect(Resource, InspectorOptions))]
+#[cfg_attr(not(feature = "inspector"), reflect(Resource))]
 pub struct EtherPet;
 
 #[derive(Component, Debug)]
 pub struct ActiveEtherPet;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Quadrants {
     TopLeft,
     TopRight,
@@ -17,6 +25,30 @@ pub enum Quadrants {
     None,
 }
 
+// Entity B walks to the side of entity A its quadrant points at:
+// TopLeft above, TopRight right, BottomLeft below, BottomRight left
+impl Quadrants {
+    pub fn conversation_side(&self) -> Option<IVec2> {
+        match self {
+            Quadrants::TopLeft => Some(ABOVE),
+            Quadrants::TopRight => Some(RIGHT),
+            Quadrants::BottomLeft => Some(BELOW),
+            Quadrants::BottomRight => Some(LEFT),
+            Quadrants::None => None,
+        }
+    }
+
+    pub fn from_conversation_side(side: IVec2) -> Self {
+        match side {
+            ABOVE => Quadrants::TopLeft,
+            RIGHT => Quadrants::TopRight,
+            BELOW => Quadrants::BottomLeft,
+            LEFT => Quadrants::BottomRight,
+            _ => Quadrants::None,
+        }
+    }
+}
+
 #[derive(Resource, Debug, Clone)]
 pub struct PairEntityConversationData {
     pub entity_a: Entity,
@@ -28,15 +60,19 @@ pub struct PairEntityConversationData {
     pub quadrant: Quadrants,
     pub chatting_icon_spawned: bool,
     pub conversation_http_request_sent: bool,
+    // tiles entity B still has to walk through to reach entity A
+    pub path: VecDeque<Vec2>,
 }
 
 #[derive(Default, Resource, Debug)]
 pub struct EntityPairConversationState {
     pub pair_entities_in_conversation: HashMap<(Entity, Entity), PairEntityConversationData>,
     pub entities_in_conversation: HashMap<Entity, bool>,
+    // pairs in range that have no reachable spot, reported once until they separate
+    pub unreachable_pairs: HashSet<(Entity, Entity)>,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BasicEmotions {
     Happiness,
     Excitement,
@@ -46,7 +82,57 @@ pub enum BasicEmotions {
     Hate,
 }
 
-#[derive(Component, Debug)]
+impl BasicEmotions {
+    pub const ALL: [BasicEmotions; 6] = [
+        BasicEmotions::Happiness,
+        BasicEmotions::Excitement,
+        BasicEmotions::Sadness,
+        BasicEmotions::Fear,
+        BasicEmotions::Disgust,
+        BasicEmotions::Hate,
+    ];
+
+    // matches the emote strings the conversation backend sends back
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BasicEmotions::Happiness => "Happiness",
+            BasicEmotions::Excitement => "Excitement",
+            BasicEmotions::Sadness => "Sadness",
+            BasicEmotions::Fear => "Fear",
+            BasicEmotions::Disgust => "Disgust",
+            BasicEmotions::Hate => "Hate",
+        }
+    }
+
+    pub fn is_positive(&self) -> bool {
+        matches!(self, BasicEmotions::Happiness | BasicEmotions::Excitement)
+    }
+
+    pub fn is_hostile(&self) -> bool {
+        matches!(self, BasicEmotions::Disgust | BasicEmotions::Hate)
+    }
+
+    // how pleasant the emotion is, from -1.0 to 1.0
+    pub fn valence(&self) -> f32 {
+        match self {
+            BasicEmotions::Happiness => 1.0,
+            BasicEmotions::Excitement => 0.8,
+            BasicEmotions::Sadness => -0.2,
+            BasicEmotions::Fear => -0.4,
+            BasicEmotions::Disgust => -0.8,
+            BasicEmotions::Hate => -1.0,
+        }
+    }
+
+    // inverse of `as_str`, used for emotes coming back from the backend
+    pub fn parse(name: &str) -> Option<Self> {
+        BasicEmotions::ALL
+            .into_iter()
+            .find(|emotion| emotion.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerData {
     pub name: String,
     pub attack_dmg: i32,
@@ -55,6 +141,21 @@ pub struct PlayerData {
     pub emotion: BasicEmotions,
 }
 
+// The pets spawned onto the farm. Simulations can swap in their own before
+// the map loads.
+#[derive(Resource, Debug, Clone)]
+pub struct PetRoster {
+    pub pets: Vec<PlayerData>,
+}
+
+impl Default for PetRoster {
+    fn default() -> Self {
+        Self {
+            pets: get_player_data(),
+        }
+    }
+}
+
 #[derive(Component, Debug)]
 pub struct PlayerUniqueId {
     pub uuid: String,
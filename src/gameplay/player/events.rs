@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use crate::prelude::*;
+
+#[derive(Event, Debug)]
 pub struct EmoteEvent {
     pub entity_uuid: String,
     pub emote: String,
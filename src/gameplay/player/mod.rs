@@ -1,20 +1,24 @@
 use crate::prelude::*;
 
-use super::state::components::CameraState;
+use super::components::FarmSet;
+use super::spritesheet::systems::spritesheets_loaded;
+use super::state::components::{CameraState, GameState};
+use super::state::simulation_running;
+use super::tile::collision::CollisionGrid;
+use super::tile::components::LoadedMap;
 
 mod systems;
 use systems::{
     chatting_icon_on_hover, chatting_icon_spawner, chatting_icon_tick, emote_event,
     emote_icon_tick, emote_movement_system, inactive_player_proximity_detection,
-    inactive_player_proximity_detection_event, inactive_player_roaming_system,
-    pair_entity_convergence_system, player_activation_system, player_confinement_system,
-    player_input, player_movement_system, player_spawner, player_sprite_animation,
-    queue_pair_entity_conversation_request, tick_player_interaction_timers,
+    inactive_player_proximity_detection_event, pair_entity_convergence_system,
+    player_activation_system, player_confinement_system, player_input, player_movement_system,
+    player_spawner, player_sprite_animation, tick_player_interaction_timers,
     update_app_state_system,
 };
 
 pub mod components;
-use components::EntityPairConversationState;
+use components::{EntityPairConversationState, PetRoster};
 
 pub mod events;
 use events::EmoteEvent;
@@ -24,27 +28,57 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(EntityPairConversationState::default())
+            .init_resource::<PetRoster>()
             .add_event::<EmoteEvent>()
-            .add_system(emote_event)
-            .add_startup_system(player_spawner)
-            .add_system(inactive_player_proximity_detection)
-            .add_systems((
-                player_sprite_animation,
-                pair_entity_convergence_system,
-                tick_player_interaction_timers,
-                player_confinement_system,
-                player_movement_system,
-                player_activation_system,
-                update_app_state_system,
-                inactive_player_proximity_detection_event,
-                inactive_player_roaming_system,
-                chatting_icon_on_hover,
-                chatting_icon_spawner,
-                chatting_icon_tick,
-                queue_pair_entity_conversation_request,
-                emote_movement_system,
-            ))
-            .add_system(emote_icon_tick)
-            .add_system(player_input.run_if(in_state(CameraState::FollowPlayer)));
+            .add_systems(
+                Update,
+                player_spawner
+                    .run_if(
+                        resource_exists::<LoadedMap>
+                            .and_then(spritesheets_loaded)
+                            .and_then(run_once()),
+                    )
+                    .in_set(FarmSet::Load),
+            )
+            .add_systems(
+                Update,
+                (
+                    player_input.run_if(
+                        in_state(CameraState::FollowPlayer).and_then(in_state(GameState::Playing)),
+                    ),
+                    player_activation_system,
+                    update_app_state_system,
+                    chatting_icon_on_hover,
+                )
+                    .chain()
+                    .in_set(FarmSet::Input),
+            )
+            // emotes follow the pet where it's drawn
+            .add_systems(Update, emote_movement_system.after(FarmSet::Interpolate))
+            .add_systems(
+                FixedUpdate,
+                (
+                    player_movement_system.run_if(
+                        in_state(CameraState::FollowPlayer)
+                            .and_then(simulation_running)
+                            .and_then(resource_exists::<CollisionGrid>),
+                    ),
+                    inactive_player_proximity_detection.run_if(resource_exists::<CollisionGrid>),
+                    (
+                        player_sprite_animation,
+                        pair_entity_convergence_system.run_if(resource_exists::<CollisionGrid>),
+                        tick_player_interaction_timers,
+                        player_confinement_system,
+                        inactive_player_proximity_detection_event,
+                        chatting_icon_spawner,
+                        chatting_icon_tick,
+                    )
+                        .chain(),
+                    emote_event,
+                    emote_icon_tick,
+                )
+                    .chain()
+                    .in_set(FarmSet::Pets),
+            );
     }
 }
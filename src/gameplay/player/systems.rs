@@ -1,47 +1,49 @@
 // packages
-use bevy::sprite::collide_aabb::collide;
-use csv::Reader;
-use rand::Rng;
-use serde_json::json;
-use std::collections::hash_map::Entry;
-use std::fs::File;
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
 
 use crate::prelude::*;
 
 // components
+use crate::gameplay::audio::components::SoundEffect;
+use crate::gameplay::behaviour::components::{ActiveBehaviour, BehaviourWeights};
 use crate::gameplay::components::{
     Animation, AnimationTimers, Attack, BasicEntityCollider, ChattingIconButton, Clickable,
-    DirectionIntent, EmoteIcon, EntityActionState, GenericActions, IconAnimation,
+    DirectionIntent, EmoteIcon, EntityActionState, FarmTuning, GenericActions, IconAnimation,
     InteractionTimers, Movement, Roaming,
 };
+use crate::gameplay::dialogue::components::DialoguePartner;
+use crate::gameplay::gossip::components::RumorMemory;
+use crate::gameplay::interpolation::components::InterpolatedTranslation;
+use crate::gameplay::mood::components::{Mood, MoodSettings};
+use crate::gameplay::needs::components::Needs;
 use crate::gameplay::npc::components::NpcEntity;
+use crate::gameplay::pathfinding::astar::find_conversation_path;
 use crate::gameplay::player::components::{
     ActiveEtherPet, EmoteParentEntity, EntityPairConversationState, EtherPet,
-    PairEntityConversationData, PlayerUniqueId, Quadrants,
+    PairEntityConversationData, PetRoster, PlayerUniqueId, Quadrants,
 };
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::SimRng;
 use crate::gameplay::spritesheet::components::{
-    ChattingIconSheet, EmotionIconSheet, PlayerSpriteSheet,
+    SpriteSheet, SpriteSheets, CHATTING_ICON_SHEET, EMOTION_ICON_SHEET, PLAYER_SHEET,
 };
 use crate::gameplay::state::components::{CameraState, GameState};
-use crate::gameplay::tile::components::BasicTileCollider;
-use crate::http_request::components::{
-    ConversationHistoryResource, HttpMethod, HttpRequest, PendingConversationHttpRequests,
-};
+use crate::gameplay::tile::collision::{entity_footprint, CollisionGrid};
+use crate::gameplay::tile::components::{BasicTileCollider, LoadedMap};
+use crate::gameplay::ui::components::SideDialogueConversation;
+use crate::http_request::components::ConversationHistoryResource;
 
 // events
-use crate::gameplay::events::ConversationActionEvent;
+use crate::gameplay::audio::events::PlaySoundEffectEvent;
+use crate::gameplay::events::{
+    ConversationActionEvent, ConversationFinishedEvent, ConversationUnreachableEvent,
+};
 use crate::gameplay::player::events::EmoteEvent;
-// use crate::http_request::events::ConversationHistoryEvent;
-
-// helpers
-use crate::data::player_data::get_player_data;
-use crate::data::spawn_data::get_player_spawn_data;
 
 // systems
 use crate::gameplay::spritesheet::systems::{
     spawn_chatting_sprite, spawn_emote_sprite, spawn_player_spritesheet_sprite,
 };
-use crate::gameplay::tile::systems::basic_wall_collision_check;
 
 fn get_emotion_index(emotion: &str) -> usize {
     if emotion == "Happiness" {
@@ -89,34 +91,63 @@ pub fn player_confinement_system(mut player_query: Query<&mut Transform, With<Et
     }
 }
 
+// walks the player's pet the way the keys point
 pub fn player_movement_system(
     time: Res<Time>,
-    mut query: Query<(&mut Movement, &mut Transform), With<EtherPet>>,
+    collision_grid: Res<CollisionGrid>,
+    mut active_player_query: Query<
+        (&Movement, &mut Transform, &BasicEntityCollider),
+        (With<ActiveEtherPet>, Without<BasicTileCollider>),
+    >,
+    inactive_player_query: Query<
+        &Transform,
+        (
+            With<BasicEntityCollider>,
+            With<NpcEntity>,
+            Without<ActiveEtherPet>,
+            Without<BasicTileCollider>,
+        ),
+    >,
 ) {
-    for (player_movement, mut transform) in query.iter_mut() {
-        let direction = player_movement.direction;
+    for (player_movement, mut transform, collider) in active_player_query.iter_mut() {
+        let delta =
+            player_movement.direction * player_movement.speed * TILE_SIZE * time.delta_seconds();
+
+        // the grid slides the pet along walls, other pets still block each axis outright
+        let swept = collision_grid.sweep_aabb(
+            transform.translation.truncate(),
+            entity_footprint(collider),
+            delta,
+        );
+
+        let target = Vec3::new(swept.x, transform.translation.y, transform.translation.z);
+        if basic_player_entity_collision_check(target, &inactive_player_query) {
+            transform.translation = target;
+        }
 
-        transform.translation.x += direction.x * player_movement.speed * time.delta_seconds();
-        transform.translation.y += direction.y * player_movement.speed * time.delta_seconds();
+        let target = Vec3::new(transform.translation.x, swept.y, transform.translation.z);
+        if basic_player_entity_collision_check(target, &inactive_player_query) {
+            transform.translation = target;
+        }
     }
 }
 
 // animation
 pub fn player_sprite_animation(
     time: Res<Time>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
     mut player_query: Query<
         (
-            &mut TextureAtlasSprite,
+            &mut TextureAtlas,
             &mut Animation,
             &mut EntityActionState,
             &mut AnimationTimers,
+            &Transform,
         ),
         With<EtherPet>,
     >,
 ) {
-    for (mut spritesheet, mut player_anim, mut player_action_state, mut player_timer) in
+    for (mut spritesheet, mut player_anim, mut player_action_state, mut player_timer, transform) in
         player_query.iter_mut()
     {
         let base_index = match player_anim.last_direction {
@@ -124,24 +155,44 @@ pub fn player_sprite_animation(
                 GenericActions::Idle => 0,
                 GenericActions::Walk => 32,
                 GenericActions::Attack => 96,
+                GenericActions::IntoRest | GenericActions::OutOfRest | GenericActions::Resting => {
+                    64
+                }
+                GenericActions::Eating => 160,
+                GenericActions::Sleeping => 176,
                 _ => 0,
             },
             DirectionIntent::Up => match player_action_state.action {
                 GenericActions::Idle => 8,
                 GenericActions::Walk => 40,
                 GenericActions::Attack => 104,
+                GenericActions::IntoRest | GenericActions::OutOfRest | GenericActions::Resting => {
+                    72
+                }
+                GenericActions::Eating => 168,
+                GenericActions::Sleeping => 184,
                 _ => 0,
             },
             DirectionIntent::Left => match player_action_state.action {
                 GenericActions::Idle => 16,
                 GenericActions::Walk => 56,
                 GenericActions::Attack => 112,
+                GenericActions::IntoRest | GenericActions::OutOfRest | GenericActions::Resting => {
+                    80
+                }
+                GenericActions::Eating => 160,
+                GenericActions::Sleeping => 176,
                 _ => 0,
             },
             DirectionIntent::Right => match player_action_state.action {
                 GenericActions::Idle => 24,
                 GenericActions::Walk => 48,
                 GenericActions::Attack => 120,
+                GenericActions::IntoRest | GenericActions::OutOfRest | GenericActions::Resting => {
+                    88
+                }
+                GenericActions::Eating => 168,
+                GenericActions::Sleeping => 184,
                 _ => 0,
             },
         };
@@ -172,54 +223,83 @@ pub fn player_sprite_animation(
                         (player_anim.current_frame + 1) % player_anim.frame_count;
 
                     if player_anim.current_frame == 0 {
-                        let attack_sound_effect =
-                            asset_server.load("audio/sound_effects/normal_slash.ogg");
-                        audio.play(attack_sound_effect);
+                        sound_effect_event_writer.send(PlaySoundEffectEvent {
+                            effect: SoundEffect::NormalSlash,
+                            position: Some(transform.translation),
+                        });
                         player_timer.attack_timer.reset();
                         player_action_state.action = GenericActions::Idle;
                     }
                 }
             }
-            GenericActions::IntoRest => {}
-            GenericActions::OutOfRest => {}
-            GenericActions::Resting => {}
-            GenericActions::Sleeping => {}
+            GenericActions::IntoRest => {
+                player_timer.idle_timer.tick(time.delta());
+                if player_timer.idle_timer.just_finished() {
+                    player_anim.current_frame =
+                        (player_anim.current_frame + 1) % player_anim.frame_count;
+
+                    // lying down done, hold the resting loop
+                    if player_anim.current_frame == 0 {
+                        player_action_state.action = GenericActions::Resting;
+                    }
+                }
+            }
+            GenericActions::OutOfRest => {
+                player_timer.idle_timer.tick(time.delta());
+                if player_timer.idle_timer.just_finished() {
+                    player_anim.current_frame =
+                        (player_anim.current_frame + 1) % player_anim.frame_count;
+
+                    if player_anim.current_frame == 0 {
+                        player_action_state.action = GenericActions::Idle;
+                    }
+                }
+            }
+            GenericActions::Resting | GenericActions::Sleeping => {
+                player_timer.idle_timer.tick(time.delta());
+                if player_timer.idle_timer.just_finished() {
+                    player_anim.current_frame =
+                        (player_anim.current_frame + 1) % player_anim.frame_count;
+                }
+            }
+            GenericActions::Eating => {
+                player_timer.eat_timer.tick(time.delta());
+                if player_timer.eat_timer.just_finished() {
+                    player_anim.current_frame =
+                        (player_anim.current_frame + 1) % player_anim.frame_count;
+                }
+            }
             GenericActions::Interacting => {}
-            GenericActions::Eating => {}
             GenericActions::Emote => {}
         }
-        spritesheet.index = base_index + player_anim.current_frame;
+
+        // getting up is lying down played backwards
+        let frame = if player_action_state.action == GenericActions::OutOfRest {
+            player_anim.frame_count - 1 - player_anim.current_frame
+        } else {
+            player_anim.current_frame
+        };
+        spritesheet.index = base_index + frame;
     }
 }
 
 // player input
+// Only reads the keys, `player_movement_system` walks the pet on the next tick
 pub fn player_input(
     mut commands: Commands,
-    time: Res<Time>,
-    keyboard_input: Res<Input<KeyCode>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
     mut active_player_query: Query<
         (
             Entity,
             &mut Movement,
             &mut Animation,
             &mut EntityActionState,
-            &mut Transform,
         ),
         (With<ActiveEtherPet>, Without<BasicTileCollider>),
     >,
-    wall_query: Query<&Transform, (With<BasicTileCollider>, Without<EtherPet>)>,
-    inactive_player_query: Query<(
-        &mut Transform,
-        (
-            With<BasicEntityCollider>,
-            With<NpcEntity>,
-            Without<ActiveEtherPet>,
-            Without<BasicTileCollider>,
-        ),
-    )>,
     mut next_camera_state: ResMut<NextState<CameraState>>,
 ) {
-    for (player_entity, player_movement, mut player_anim, mut player_action_state, mut transform) in
+    for (player_entity, mut player_movement, mut player_anim, mut player_action_state) in
         active_player_query.iter_mut()
     {
         if keyboard_input.pressed(KeyCode::Escape) {
@@ -229,29 +309,28 @@ pub fn player_input(
         }
 
         let mut action = GenericActions::Idle;
-        let mut x_delta = 0.0;
+        let mut direction = Vec2::ZERO;
 
-        if keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left) {
-            x_delta -= player_movement.speed * TILE_SIZE * time.delta_seconds();
+        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
+            direction.x -= 1.0;
             player_anim.last_direction = DirectionIntent::Left;
             action = GenericActions::Walk;
         }
 
-        if keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right) {
-            x_delta += player_movement.speed * TILE_SIZE * time.delta_seconds();
+        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
+            direction.x += 1.0;
             player_anim.last_direction = DirectionIntent::Right;
             action = GenericActions::Walk;
         }
 
-        let mut y_delta = 0.0;
-        if keyboard_input.pressed(KeyCode::W) || keyboard_input.pressed(KeyCode::Up) {
-            y_delta += player_movement.speed * TILE_SIZE * time.delta_seconds();
+        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
+            direction.y += 1.0;
             player_anim.last_direction = DirectionIntent::Up;
             action = GenericActions::Walk;
         }
 
-        if keyboard_input.pressed(KeyCode::S) || keyboard_input.pressed(KeyCode::Down) {
-            y_delta -= player_movement.speed * TILE_SIZE * time.delta_seconds();
+        if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
+            direction.y -= 1.0;
             player_anim.last_direction = DirectionIntent::Down;
             action = GenericActions::Walk;
         }
@@ -263,176 +342,148 @@ pub fn player_input(
         if player_action_state.action != GenericActions::Attack {
             player_action_state.action = action;
         }
-
-        let target = transform.translation + Vec3::new(x_delta, 0.0, 0.0);
-        if basic_wall_collision_check(target, &wall_query)
-            && basic_player_entity_collision_check(target, &inactive_player_query)
-        {
-            transform.translation = target;
-        }
-
-        let target = transform.translation + Vec3::new(0.0, y_delta, 0.0);
-        if basic_wall_collision_check(target, &wall_query)
-            && basic_player_entity_collision_check(target, &inactive_player_query)
-        {
-            transform.translation = target;
-        }
+        player_movement.direction = direction;
     }
 }
 
 // spawner
-pub fn player_spawner(mut commands: Commands, player_spritesheet: Res<PlayerSpriteSheet>) {
-    let spawn_data = get_player_spawn_data();
-    let mut player_amount = 0;
-    let all_player_data = get_player_data();
-
-    for spawn_data in spawn_data {
-        let spawn_points_file = match File::open(spawn_data.csv_file_path) {
-            Ok(file) => file,
-            Err(error) => panic!("Failed to open spawn points file: {:?}", error),
-        };
-        let mut reader = Reader::from_reader(spawn_points_file);
-        let mut records: Vec<_> = reader.records().collect();
-        let map_height = records.len();
-        let map_width = records[0].as_mut().unwrap().len();
-
-        let half_map_width = (map_width as f32 * TILE_SIZE) / 2.0;
-        let half_map_height = (map_height as f32 * TILE_SIZE) / 2.0;
-
-        for (y, record_result) in records.iter().enumerate() {
-            let record: &csv::StringRecord = record_result.as_ref().unwrap();
-            for (x, cell) in record.iter().enumerate() {
-                let tile_index = cell.parse::<i32>().unwrap();
-                let tile_translation = Vec3::new(
-                    x as f32 * TILE_SIZE - half_map_width,
-                    -(y as f32) * TILE_SIZE + half_map_height,
-                    spawn_data.z_index,
-                );
-
-                if tile_index != -1 && player_amount < all_player_data.len() {
-                    let player_data = &all_player_data[player_amount];
-                    let player_entity = spawn_player_spritesheet_sprite(
-                        &mut commands,
-                        &player_spritesheet,
-                        0,
-                        tile_translation,
-                    );
-
-                    commands
-                        .entity(player_entity)
-                        .insert(Name::new(player_data.name.clone()))
-                        .insert(EtherPet)
-                        .insert(Clickable)
-                        .insert(PlayerUniqueId {
-                            uuid: player_data.player_id.clone(),
-                        })
-                        .insert(BasicEntityCollider {
-                            width: 16.0,
-                            height: 16.0,
-                            collided: false,
-                        })
-                        .insert(AnimationTimers {
-                            idle_timer: Timer::from_seconds(0.25, TimerMode::Repeating),
-                            walk_timer: Timer::from_seconds(0.075, TimerMode::Repeating),
-                            attack_timer: Timer::from_seconds(0.1, TimerMode::Once),
-                            eat_timer: Timer::from_seconds(0.075, TimerMode::Repeating),
-                            emote_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
-                        })
-                        .insert(EntityActionState {
-                            action: GenericActions::Idle,
-                        })
-                        .insert(Animation {
-                            last_direction: DirectionIntent::Down,
-                            current_frame: 0,
-                            frame_count: 8,
-                        })
-                        .insert(Attack {
-                            attack_active: false,
-                            attack_dmg: player_data.attack_dmg,
-                        })
-                        .insert(Movement {
-                            direction: Vec2::ZERO,
-                            speed: player_data.speed,
-                        })
-                        .insert(Roaming {
-                            // roam
-                            roam_direction: Vec3::ZERO,
-                            roam_time: 0.0,
-                            roam_max_time: 5.0,
-                            roam_speed: player_data.speed,
-                            // idle
-                            is_idle: false,
-                            idle_time: 0.0,
-                            idle_max_time: 2.0,
-                            // eat
-                            is_eating: false,
-                            eating_time: 0.0,
-                            eating_max_time: 3.0,
-                            // emote
-                            is_emoting: false,
-                            emote_time: 0.0,
-                            emote_max_time: 3.0,
-                        })
-                        .insert(InteractionTimers {
-                            // when character is chatting with another character
-                            prox_chat_timer: Timer::from_seconds(
-                                ENTITY_CONVO_DURATION,
-                                TimerMode::Once,
-                            ),
-                            prox_chat_active: false,
-                            // when cooldown timer is active characters cannot chat
-                            prox_chat_cooldown_timer: Timer::from_seconds(15.0, TimerMode::Once),
-                            can_prox_chat: true,
-                        })
-                        .insert(NpcEntity);
+pub fn player_spawner(
+    mut commands: Commands,
+    sprite_sheets: Res<SpriteSheets>,
+    loaded_map: Res<LoadedMap>,
+    mood_settings: Res<MoodSettings>,
+    pet_roster: Res<PetRoster>,
+    sim_rng: Res<SimRng>,
+    tuning: Res<FarmTuning>,
+) {
+    let Some(player_spritesheet) = sprite_sheets.get(PLAYER_SHEET) else {
+        error!("No {:?} spritesheet in the manifest", PLAYER_SHEET);
+        return;
+    };
+    let spawn_points = loaded_map.spawn_points("character_spawn");
+    let all_player_data = &pet_roster.pets;
+
+    if spawn_points.len() < all_player_data.len() {
+        warn!(
+            "Map only has {} character spawn points for {} pets",
+            spawn_points.len(),
+            all_player_data.len()
+        );
+    }
 
-                    player_amount += 1;
-                }
+    for (player_data, spawn_point) in all_player_data.iter().zip(spawn_points.iter()) {
+        let player_entity =
+            spawn_player_spritesheet_sprite(&mut commands, player_spritesheet, 0, *spawn_point);
 
-                if player_amount == all_player_data.len() {
-                    break;
-                }
-            }
-        }
+        commands
+            .entity(player_entity)
+            .insert(Name::new(player_data.name.clone()))
+            .insert(InterpolatedTranslation::new(*spawn_point))
+            .insert(EtherPet)
+            .insert(Clickable)
+            .insert(PlayerUniqueId {
+                uuid: player_data.player_id.clone(),
+            })
+            .insert(player_data.clone())
+            .insert(Mood::new(
+                player_data.emotion,
+                mood_settings.temperament_level,
+            ))
+            .insert(Needs::default())
+            .insert(ActiveBehaviour::default())
+            .insert(BehaviourWeights::default())
+            .insert(RumorMemory::default())
+            .insert(sim_rng.stream(&player_data.player_id))
+            .insert(BasicEntityCollider {
+                width: 16.0,
+                height: 16.0,
+                collided: false,
+            })
+            .insert(AnimationTimers {
+                idle_timer: Timer::from_seconds(0.25, TimerMode::Repeating),
+                walk_timer: Timer::from_seconds(0.075, TimerMode::Repeating),
+                attack_timer: Timer::from_seconds(0.1, TimerMode::Once),
+                eat_timer: Timer::from_seconds(0.075, TimerMode::Repeating),
+                emote_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+            })
+            .insert(EntityActionState {
+                action: GenericActions::Idle,
+            })
+            .insert(Animation {
+                last_direction: DirectionIntent::Down,
+                current_frame: 0,
+                frame_count: 8,
+            })
+            .insert(Attack {
+                attack_active: false,
+                attack_dmg: player_data.attack_dmg,
+            })
+            .insert(Movement {
+                direction: Vec2::ZERO,
+                speed: player_data.speed,
+            })
+            .insert(Roaming {
+                roam_speed: player_data.speed,
+                roam_max_time: tuning.roam_max_time,
+                idle_max_time: tuning.idle_max_time,
+            })
+            .insert(InteractionTimers {
+                // when character is chatting with another character
+                prox_chat_timer: Timer::from_seconds(tuning.convo_duration, TimerMode::Once),
+                prox_chat_active: false,
+                // when cooldown timer is active characters cannot chat
+                prox_chat_cooldown_timer: Timer::from_seconds(
+                    tuning.chat_cooldown_seconds,
+                    TimerMode::Once,
+                ),
+                can_prox_chat: true,
+            })
+            .insert(NpcEntity);
     }
 }
 
 pub fn basic_player_entity_collision_check(
     target_player_pos: Vec3,
-    inactive_player_query: &Query<(
-        &mut Transform,
+    inactive_player_query: &Query<
+        &Transform,
         (
             With<BasicEntityCollider>,
             With<NpcEntity>,
             Without<ActiveEtherPet>,
             Without<BasicTileCollider>,
         ),
-    )>,
+    >,
 ) -> bool {
+    let target = Aabb2d::new(
+        target_player_pos.truncate(),
+        Vec2::splat(TILE_SIZE * 0.01 / 2.0),
+    );
     for in_active_player in inactive_player_query.iter() {
-        let collision = collide(
-            target_player_pos,
-            Vec2::splat(TILE_SIZE * 0.01),
-            in_active_player.0.translation,
-            Vec2::splat(TILE_SIZE),
+        let collider = Aabb2d::new(
+            in_active_player.translation.truncate(),
+            Vec2::splat(TILE_SIZE / 2.0),
         );
-        if collision.is_some() {
+        if target.intersects(&collider) {
             return false;
         }
     }
-    return true;
+    true
 }
 
 // other
 pub fn player_activation_system(
     mut commands: Commands,
-    mouse_button_input: Res<Input<MouseButton>>,
-    mut windows: Query<&mut Window>,
-    mut camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
     mut player_query: Query<(Entity, &Transform), (With<EtherPet>, With<Clickable>)>,
 ) {
-    let (camera, camera_transform) = camera_query.single_mut();
-    let window = windows.single_mut();
+    // nothing to click on without a window, e.g. a headless simulation
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (windows.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
 
     if mouse_button_input.just_pressed(MouseButton::Left) {
         if let Some(world_pos) = window
@@ -441,11 +492,12 @@ pub fn player_activation_system(
             .map(|ray| ray.origin)
         {
             for (player_entity, transform) in player_query.iter_mut() {
-                let player_pos = transform.translation;
+                let player_pos = transform.translation.truncate();
                 let player_size = Vec2::new(16.0, 16.0);
-                let collision = collide(world_pos, Vec2::ZERO, player_pos, player_size);
+                let clicked = Aabb2d::new(player_pos, player_size / 2.0)
+                    .intersects(&Aabb2d::new(world_pos.truncate(), Vec2::ZERO));
 
-                if collision.is_some() {
+                if clicked {
                     commands.entity(player_entity).insert(ActiveEtherPet);
                     commands.entity(player_entity).remove::<NpcEntity>();
                 } else {
@@ -459,15 +511,15 @@ pub fn player_activation_system(
 
 fn find_quadrant(entity_a: Vec3, entity_b: Vec3) -> Quadrants {
     if entity_b.y < entity_a.y && entity_b.x > entity_a.x {
-        return Quadrants::BottomRight;
+        Quadrants::BottomRight
     } else if entity_b.y < entity_a.y && entity_b.x < entity_a.x {
-        return Quadrants::BottomLeft;
+        Quadrants::BottomLeft
     } else if entity_b.y > entity_a.y && entity_b.x > entity_a.x {
-        return Quadrants::TopRight;
+        Quadrants::TopRight
     } else if entity_b.y > entity_a.y && entity_b.x < entity_a.x {
-        return Quadrants::TopLeft;
+        Quadrants::TopLeft
     } else {
-        return Quadrants::None;
+        Quadrants::None
     }
 }
 
@@ -481,13 +533,13 @@ pub fn update_app_state_system(
     let active_player_exists = player_query.iter().count() > 0;
 
     if active_player_exists
-        && camera_state.0 != CameraState::FollowPlayer
-        && game_state.0 == GameState::Playing
+        && *camera_state.get() != CameraState::FollowPlayer
+        && *game_state.get() == GameState::Playing
     {
         next_camera_state.set(CameraState::FollowPlayer);
     } else if !active_player_exists
-        && camera_state.0 != CameraState::ManualCameraControl
-        && game_state.0 == GameState::Playing
+        && *camera_state.get() != CameraState::ManualCameraControl
+        && *game_state.get() == GameState::Playing
     {
         next_camera_state.set(CameraState::ManualCameraControl);
     }
@@ -505,9 +557,19 @@ pub fn inactive_player_proximity_detection(
             &Animation,
             &PlayerUniqueId,
         ),
-        (With<EtherPet>, Without<ActiveEtherPet>),
+        (
+            With<EtherPet>,
+            Without<ActiveEtherPet>,
+            Without<DialoguePartner>,
+        ),
     >,
+    moods_query: Query<&Mood>,
+    behaviours_query: Query<&ActiveBehaviour>,
+    collision_grid: Res<CollisionGrid>,
+    relationship_graph: Res<RelationshipGraph>,
+    tuning: Res<FarmTuning>,
     mut http_request_event_writer: EventWriter<ConversationActionEvent>,
+    mut unreachable_event_writer: EventWriter<ConversationUnreachableEvent>,
 ) {
     let inactive_players: Vec<(
         Entity,
@@ -555,45 +617,99 @@ pub fn inactive_player_proximity_detection(
 
                 let distance_from_each_other = pos_a.distance(pos_b);
                 let quadrant = find_quadrant(pos_a, pos_b);
-                if distance_from_each_other < ENTITY_CONVO_DISTANCE
+                if distance_from_each_other < tuning.convo_distance
                     && distance_from_each_other > 10.
                     && interaction_timer_a.can_prox_chat
                     && interaction_timer_b.can_prox_chat
                 {
-                    match entity_pair_convo_state
+                    if entity_pair_convo_state
                         .pair_entities_in_conversation
-                        .entry((entity_a, entity_b))
+                        .contains_key(&(entity_a, entity_b))
+                    {
+                        continue;
+                    }
+
+                    // pets that can't stand each other walk on by
+                    if relationship_graph.avoids(&entity_a_id.uuid, &entity_b_id.uuid)
+                        || relationship_graph.avoids(&entity_b_id.uuid, &entity_a_id.uuid)
+                    {
+                        continue;
+                    }
+
+                    // and so do pets that aren't in the mood to talk
+                    if let Ok([mood_a, mood_b]) = moods_query.get_many([entity_a, entity_b]) {
+                        if !mood_a.wants_to_chat_with(mood_b) {
+                            continue;
+                        }
+                    }
+
+                    // a pet that's eating or asleep isn't disturbed
+                    if behaviours_query
+                        .get_many([entity_a, entity_b])
+                        .is_ok_and(|behaviours| {
+                            behaviours.iter().any(|active| active.is_occupied())
+                        })
                     {
-                        Entry::Occupied(_) => {}
-                        Entry::Vacant(entry) => {
-                            entry.insert(PairEntityConversationData {
+                        continue;
+                    }
+
+                    // already known to be out of reach, don't search again every tick
+                    if entity_pair_convo_state
+                        .unreachable_pairs
+                        .contains(&(entity_a, entity_b))
+                    {
+                        continue;
+                    }
+
+                    // entity B walks over to entity A, only start chatting if it can get there
+                    let Some(conversation_path) = find_conversation_path(
+                        &collision_grid,
+                        pos_b.truncate(),
+                        pos_a.truncate(),
+                        quadrant.conversation_side(),
+                        tuning.convo_distance,
+                    ) else {
+                        entity_pair_convo_state
+                            .unreachable_pairs
+                            .insert((entity_a, entity_b));
+                        unreachable_event_writer
+                            .send(ConversationUnreachableEvent { entity_a, entity_b });
+                        continue;
+                    };
+
+                    entity_pair_convo_state
+                        .pair_entities_in_conversation
+                        .insert(
+                            (entity_a, entity_b),
+                            PairEntityConversationData {
                                 entity_a,
                                 entity_b,
                                 entity_a_pos: pos_a,
                                 entity_b_pos: pos_b,
                                 entity_a_id: entity_a_id.uuid.clone(),
                                 entity_b_id: entity_b_id.uuid.clone(),
-                                quadrant,
+                                quadrant: Quadrants::from_conversation_side(conversation_path.side),
                                 chatting_icon_spawned: false,
                                 conversation_http_request_sent: false,
-                            });
-
-                            // Also mark both entities as in use.
-                            entity_pair_convo_state
-                                .entities_in_conversation
-                                .insert(entity_a, true);
-                            entity_pair_convo_state
-                                .entities_in_conversation
-                                .insert(entity_b, true);
-                            // TODO: Add logic to stop event from firing if player is going to hit a collider
-                            http_request_event_writer
-                                .send(ConversationActionEvent { entity_a, entity_b });
-                        }
-                    }
+                                path: conversation_path.waypoints.into(),
+                            },
+                        );
+
+                    // Also mark both entities as in use.
+                    entity_pair_convo_state
+                        .entities_in_conversation
+                        .insert(entity_a, true);
+                    entity_pair_convo_state
+                        .entities_in_conversation
+                        .insert(entity_b, true);
+                    http_request_event_writer.send(ConversationActionEvent { entity_a, entity_b });
                 } else {
                     entity_pair_convo_state
                         .pair_entities_in_conversation
                         .remove(&(entity_a, entity_b));
+                    entity_pair_convo_state
+                        .unreachable_pairs
+                        .remove(&(entity_a, entity_b));
 
                     // Also mark both entities as not in use.
                     entity_pair_convo_state
@@ -609,6 +725,7 @@ pub fn inactive_player_proximity_detection(
 }
 
 pub fn pair_entity_convergence_system(
+    time: Res<Time>,
     mut entity_pair_convo_state: ResMut<EntityPairConversationState>,
     mut entities_query: Query<
         (
@@ -616,156 +733,87 @@ pub fn pair_entity_convergence_system(
             &mut EntityActionState,
             &mut Animation,
             &Movement,
-            &PlayerUniqueId,
+            &BasicEntityCollider,
         ),
         (With<EtherPet>, Without<ActiveEtherPet>),
     >,
-    wall_query: Query<&Transform, (With<BasicTileCollider>, Without<EtherPet>)>,
+    collision_grid: Res<CollisionGrid>,
 ) {
-    let mut entities_to_update = Vec::new();
     let mut entities_to_remove = Vec::new();
 
-    // First loop: gather all data
-    for ((entity_a, entity_b), pair_convo_data) in
-        entity_pair_convo_state.pair_entities_in_conversation.iter()
+    for pair_convo_data in entity_pair_convo_state
+        .pair_entities_in_conversation
+        .values_mut()
     {
-        if let Ok((transform_a, mut entity_a_action_state, mut animation_a, _, entity_a_uuid)) =
-            entities_query.get_mut(*entity_a)
+        let (direction_a, direction_b) = match pair_convo_data.quadrant {
+            Quadrants::TopLeft => (DirectionIntent::Up, DirectionIntent::Down),
+            Quadrants::TopRight => (DirectionIntent::Right, DirectionIntent::Left),
+            Quadrants::BottomLeft => (DirectionIntent::Down, DirectionIntent::Up),
+            Quadrants::BottomRight => (DirectionIntent::Left, DirectionIntent::Right),
+            Quadrants::None => continue,
+        };
+
+        // entity A waits for entity B to walk over
+        if let Ok((_, mut entity_a_action_state, mut animation_a, _, _)) =
+            entities_query.get_mut(pair_convo_data.entity_a)
         {
-            let entity_a_uuid = entity_a_uuid.uuid.clone();
             entity_a_action_state.action = GenericActions::Idle;
-            match pair_convo_data.quadrant {
-                Quadrants::TopLeft => {
-                    animation_a.last_direction = DirectionIntent::Up;
-                }
-                Quadrants::TopRight => {
-                    animation_a.last_direction = DirectionIntent::Right;
-                }
-                Quadrants::BottomLeft => {
-                    animation_a.last_direction = DirectionIntent::Down;
-                }
-                Quadrants::BottomRight => {
-                    animation_a.last_direction = DirectionIntent::Left;
-                }
-                Quadrants::None => {}
-            }
-
-            entities_to_update.push((
-                *entity_a,
-                *entity_b,
-                transform_a.translation,
-                pair_convo_data,
-                entity_a_uuid,
-            ));
+            animation_a.last_direction = direction_a;
         }
-    }
 
-    // Second loop: perform updates based on gathered data
-    for (_, entity_b, entity_a_transform, pair_convo_data, entity_a_uuid) in
-        entities_to_update.iter()
-    {
-        if let Ok((
+        let Ok((
             mut transform_b,
             mut entity_b_action_state,
             mut animation_b,
             movement_b,
-            entity_b_uuid,
-        )) = entities_query.get_mut(*entity_b)
-        {
-            let _entity_b_uuid = entity_b_uuid.uuid.clone();
-            let _entity_a_uuid = entity_a_uuid.clone();
-
-            match pair_convo_data.quadrant {
-                Quadrants::TopLeft => {
-                    animation_b.last_direction = DirectionIntent::Down;
-                    if transform_b.translation.x.round() == entity_a_transform.x.round() {
-                        entity_b_action_state.action = GenericActions::Idle;
-                    } else {
-                        entity_b_action_state.action = GenericActions::Walk;
-                        let x_value = entity_a_transform.x;
-                        let y_value = entity_a_transform.y + ENTITY_CONVO_DISTANCE;
-                        let target = Vec3::new(x_value, y_value, 0.0);
-                        if basic_wall_collision_check(target, &wall_query) {
-                            let direction_to_target = target - transform_b.translation;
-                            let delta = direction_to_target.normalize() * movement_b.speed;
-
-                            transform_b.translation.x += delta.x;
-                            transform_b.translation.y += delta.y;
-                        } else {
-                            entity_b_action_state.action = GenericActions::Idle;
-                            entities_to_remove.push(*entity_b);
-                        }
-                    }
-                }
-                Quadrants::TopRight => {
-                    animation_b.last_direction = DirectionIntent::Left;
-                    if transform_b.translation.y.round() == entity_a_transform.y.round() {
-                        entity_b_action_state.action = GenericActions::Idle;
-                    } else {
-                        entity_b_action_state.action = GenericActions::Walk;
-                        let y_value = entity_a_transform.y;
-                        let x_value = entity_a_transform.x + ENTITY_CONVO_DISTANCE;
-
-                        let target = Vec3::new(x_value, y_value, 0.0);
-                        if basic_wall_collision_check(target, &wall_query) {
-                            let direction_to_target = target - transform_b.translation;
-                            let delta = direction_to_target.normalize() * movement_b.speed;
-
-                            transform_b.translation.x += delta.x;
-                            transform_b.translation.y += delta.y;
-                        } else {
-                            entity_b_action_state.action = GenericActions::Idle;
-                            entities_to_remove.push(*entity_b);
-                        }
-                    }
-                }
-                Quadrants::BottomLeft => {
-                    animation_b.last_direction = DirectionIntent::Up;
-                    if transform_b.translation.x.round() == entity_a_transform.x.round() {
-                        entity_b_action_state.action = GenericActions::Idle;
-                    } else {
-                        entity_b_action_state.action = GenericActions::Walk;
-                        let x_value = entity_a_transform.x;
-                        let y_value = entity_a_transform.y - ENTITY_CONVO_DISTANCE;
-
-                        let target = Vec3::new(x_value, y_value, 0.0);
-                        if basic_wall_collision_check(target, &wall_query) {
-                            let direction_to_target = target - transform_b.translation;
-                            let delta = direction_to_target.normalize() * movement_b.speed;
-
-                            transform_b.translation.x += delta.x;
-                            transform_b.translation.y += delta.y;
-                        } else {
-                            entity_b_action_state.action = GenericActions::Idle;
-                            entities_to_remove.push(*entity_b);
-                        }
-                    }
-                }
-                Quadrants::BottomRight => {
-                    animation_b.last_direction = DirectionIntent::Right;
-                    if transform_b.translation.y.round() == entity_a_transform.y.round() {
-                        entity_b_action_state.action = GenericActions::Idle;
-                    } else {
-                        entity_b_action_state.action = GenericActions::Walk;
-                        let y_value = entity_a_transform.y;
-                        let x_value = entity_a_transform.x - 12.;
-
-                        let target = Vec3::new(x_value, y_value, 0.0);
-                        if basic_wall_collision_check(target, &wall_query) {
-                            let direction_to_target = target - transform_b.translation;
-                            let delta = direction_to_target.normalize() * movement_b.speed;
-
-                            transform_b.translation.x += delta.x;
-                            transform_b.translation.y += delta.y;
-                        } else {
-                            entity_b_action_state.action = GenericActions::Idle;
-                            entities_to_remove.push(*entity_b);
-                        }
-                    }
-                }
-                Quadrants::None => {}
+            collider_b,
+        )) = entities_query.get_mut(pair_convo_data.entity_b)
+        else {
+            continue;
+        };
+
+        let Some(waypoint) = pair_convo_data.path.front().copied() else {
+            entity_b_action_state.action = GenericActions::Idle;
+            animation_b.last_direction = direction_b;
+            continue;
+        };
+
+        entity_b_action_state.action = GenericActions::Walk;
+        let position = transform_b.translation.truncate();
+        let to_waypoint = waypoint - position;
+        // tiles per second, the same as roaming
+        let step = movement_b.speed * TILE_SIZE * time.delta_seconds();
+
+        if to_waypoint.length() <= step {
+            transform_b.translation.x = waypoint.x;
+            transform_b.translation.y = waypoint.y;
+            pair_convo_data.path.pop_front();
+            continue;
+        }
+
+        let delta = to_waypoint.normalize() * step;
+        animation_b.last_direction = if delta.x.abs() > delta.y.abs() {
+            if delta.x > 0.0 {
+                DirectionIntent::Right
+            } else {
+                DirectionIntent::Left
             }
+        } else if delta.y > 0.0 {
+            DirectionIntent::Up
+        } else {
+            DirectionIntent::Down
+        };
+
+        let swept = collision_grid.sweep_aabb(position, entity_footprint(collider_b), delta);
+        if swept == position {
+            // the path was planned from the tile centers, give up if the
+            // pet's feet still manage to catch on a corner
+            entity_b_action_state.action = GenericActions::Idle;
+            entities_to_remove.push(pair_convo_data.entity_b);
+            continue;
         }
+        transform_b.translation.x = swept.x;
+        transform_b.translation.y = swept.y;
     }
 
     // Finally, remove entities as needed
@@ -778,8 +826,8 @@ pub fn inactive_player_proximity_detection_event(
     mut action_event_reader: EventReader<ConversationActionEvent>,
     mut entities_query: Query<&mut InteractionTimers, (With<EtherPet>, Without<ActiveEtherPet>)>,
 ) {
-    for event in action_event_reader.iter() {
-        println!("Event: {:?}", event);
+    for event in action_event_reader.read() {
+        debug!("Event: {:?}", event);
 
         if let Ok(mut inactive_player_a) = entities_query.get_mut(event.entity_a) {
             if inactive_player_a.can_prox_chat {
@@ -804,6 +852,7 @@ pub fn tick_player_interaction_timers(
     mut entity_pair_convo_state: ResMut<EntityPairConversationState>,
     mut conversation_response_resource: ResMut<ConversationHistoryResource>,
     mut emote_event_write: EventWriter<EmoteEvent>,
+    mut conversation_finished_event_writer: EventWriter<ConversationFinishedEvent>,
 ) {
     let delta_seconds = time.delta();
     for (entity, mut interaction_timers) in inactive_player_query.iter_mut() {
@@ -837,121 +886,21 @@ pub fn tick_player_interaction_timers(
                 });
             }
 
-            remove_entity_from_conversation(entity, &mut entity_pair_convo_state);
-        }
-    }
-}
-
-pub fn inactive_player_roaming_system(
-    time: Res<Time>,
-    wall_query: Query<&Transform, (With<BasicTileCollider>, Without<EtherPet>)>,
-    mut npc_query: Query<
-        (
-            &mut Animation,
-            &mut Roaming,
-            &mut EntityActionState,
-            &mut Transform,
-            &mut BasicEntityCollider,
-            &mut InteractionTimers,
-        ),
-        (
-            With<NpcEntity>,
-            With<BasicEntityCollider>,
-            With<EtherPet>,
-            Without<ActiveEtherPet>,
-            Without<BasicTileCollider>,
-        ),
-    >,
-) {
-    for (
-        mut entity_anim,
-        mut entity_roaming_data,
-        mut entity_action_state,
-        mut transform,
-        mut collision_state,
-        interaction_timers,
-    ) in npc_query.iter_mut()
-    {
-        if interaction_timers.prox_chat_active == false {
-            // Emote
-            if entity_roaming_data.is_emoting {
-                entity_action_state.action = GenericActions::Emote;
-                entity_roaming_data.emote_time += time.delta_seconds();
-                if entity_roaming_data.emote_time > entity_roaming_data.emote_max_time {
-                    entity_roaming_data.is_emoting = false;
-                    entity_roaming_data.emote_time = 0.0;
-                }
-                continue;
-            }
-
-            // Eating
-            if entity_roaming_data.is_eating {
-                entity_action_state.action = GenericActions::Eating;
-                entity_roaming_data.eating_time += time.delta_seconds();
-                if entity_roaming_data.eating_time > entity_roaming_data.eating_max_time {
-                    entity_roaming_data.is_eating = false;
-                    entity_roaming_data.eating_time = 0.0;
-                }
-                continue;
-            }
-
-            // Roaming
-            if entity_roaming_data.is_idle {
-                entity_action_state.action = GenericActions::Idle;
-                entity_roaming_data.idle_time += time.delta_seconds();
-                if entity_roaming_data.idle_time > entity_roaming_data.idle_max_time {
-                    entity_roaming_data.is_idle = false;
-                    entity_roaming_data.idle_time = 0.0;
-                }
-                continue;
-            }
-
-            entity_roaming_data.roam_time += time.delta_seconds();
-
-            let target = transform.translation
-                + entity_roaming_data.roam_direction
-                    * entity_roaming_data.roam_speed
-                    * TILE_SIZE
-                    * time.delta_seconds();
-
-            if entity_roaming_data.roam_time > entity_roaming_data.roam_max_time
-                || entity_roaming_data.eating_time > entity_roaming_data.eating_max_time
-                || entity_roaming_data.emote_time > entity_roaming_data.emote_max_time
-                || entity_roaming_data.roam_direction == Vec3::ZERO
-                || !basic_wall_collision_check(target, &wall_query)
-                || collision_state.collided
+            // only pairs that met up count, the first of the two to finish
+            // sends it and removes the pair
+            for pair_convo_data in entity_pair_convo_state
+                .pair_entities_in_conversation
+                .values()
+                .filter(|pair| pair.entity_a == entity || pair.entity_b == entity)
+                .filter(|pair| pair.path.is_empty())
             {
-                let mut rng = rand::thread_rng();
-                if rng.gen_bool(0.3) {
-                    continue;
-                } else {
-                    entity_roaming_data.roam_direction =
-                        Vec3::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0), 0.0)
-                            .normalize();
-                    entity_roaming_data.roam_time = 0.0;
-                    entity_anim.last_direction = match (
-                        entity_roaming_data.roam_direction.x.signum(),
-                        entity_roaming_data.roam_direction.y.signum(),
-                    ) {
-                        (x, y) if x > 0.0 && y.abs() <= x => DirectionIntent::Right,
-                        (x, y) if x < 0.0 && y.abs() <= -x => DirectionIntent::Left,
-                        (_, y) if y > 0.0 => DirectionIntent::Up,
-                        _ => DirectionIntent::Down,
-                    };
-                    entity_action_state.action = GenericActions::Walk;
-                }
-            }
-
-            // Roam
-            if basic_wall_collision_check(target, &wall_query) && !collision_state.collided {
-                transform.translation = target;
-                entity_action_state.action = GenericActions::Walk;
-            } else {
-                entity_roaming_data.is_idle = true;
-                entity_action_state.action = GenericActions::Idle;
+                conversation_finished_event_writer.send(ConversationFinishedEvent {
+                    entity_a: pair_convo_data.entity_a,
+                    entity_b: pair_convo_data.entity_b,
+                });
             }
 
-            collision_state.collided = false;
+            remove_entity_from_conversation(entity, &mut entity_pair_convo_state);
         }
     }
 }
@@ -959,7 +908,8 @@ pub fn inactive_player_roaming_system(
 pub fn chatting_icon_tick(
     mut commands: Commands,
     time: Res<Time>,
-    mut chatting_icons_query: Query<(Entity, &mut TextureAtlasSprite, &mut ChattingIconButton)>,
+    tuning: Res<FarmTuning>,
+    mut chatting_icons_query: Query<(Entity, &mut TextureAtlas, &mut ChattingIconButton)>,
 ) {
     let delta_seconds = time.delta();
     for (chatting_icon_entity, mut spritesheet, mut chatting_icon_button) in
@@ -974,7 +924,7 @@ pub fn chatting_icon_tick(
             .elapsed()
             .as_secs_f32();
         let num_frames = 4;
-        let total_duration = ENTITY_CONVO_DURATION * 0.8;
+        let total_duration = tuning.convo_duration * 0.8;
         let frame_duration = total_duration / (num_frames - 1) as f32 / 4.5;
 
         let base_index = if elapsed < frame_duration {
@@ -1000,8 +950,12 @@ pub fn chatting_icon_tick(
 pub fn chatting_icon_spawner(
     mut commands: Commands,
     mut entity_pair_convo_state: ResMut<EntityPairConversationState>,
-    chatting_icon_sheet: Res<ChattingIconSheet>,
+    sprite_sheets: Res<SpriteSheets>,
+    tuning: Res<FarmTuning>,
 ) {
+    let Some(chatting_icon_sheet) = sprite_sheets.get(CHATTING_ICON_SHEET) else {
+        return;
+    };
     for (_, pair_convo_data) in entity_pair_convo_state
         .pair_entities_in_conversation
         .iter_mut()
@@ -1010,25 +964,27 @@ pub fn chatting_icon_spawner(
             let icon_translation: Vec3 = match pair_convo_data.quadrant {
                 Quadrants::TopLeft => pair_convo_data.entity_a_pos + Vec3::new(0.0, 20., 0.0),
                 Quadrants::TopRight => {
-                    pair_convo_data.entity_a_pos + Vec3::new(ENTITY_CONVO_DISTANCE / 2., 15.0, 0.0)
+                    pair_convo_data.entity_a_pos + Vec3::new(tuning.convo_distance / 2., 15.0, 0.0)
                 }
                 Quadrants::BottomLeft => pair_convo_data.entity_a_pos + Vec3::new(0.0, 15.0, 0.0),
                 Quadrants::BottomRight => {
                     pair_convo_data.entity_a_pos
-                        + Vec3::new(-(ENTITY_CONVO_DISTANCE / 2.), 15.0, 0.0)
+                        + Vec3::new(-(tuning.convo_distance / 2.), 15.0, 0.0)
                 }
                 Quadrants::None => Vec3::ZERO,
             };
             let chatting_icon_entity: Entity =
-                spawn_chatting_sprite(&mut commands, &chatting_icon_sheet, 0, icon_translation);
+                spawn_chatting_sprite(&mut commands, chatting_icon_sheet, 0, icon_translation);
 
             commands
                 .entity(chatting_icon_entity)
                 .insert(ChattingIconButton {
                     chatting_icon_despawn_timer: Timer::from_seconds(
-                        ENTITY_CONVO_DURATION * 0.9,
+                        tuning.convo_duration * 0.9,
                         TimerMode::Once,
                     ),
+                    entity_a_uuid: pair_convo_data.entity_a_id.clone(),
+                    entity_b_uuid: pair_convo_data.entity_b_id.clone(),
                 })
                 .insert(IconAnimation {
                     current_frame: 0,
@@ -1039,14 +995,24 @@ pub fn chatting_icon_spawner(
     }
 }
 
+// clicking a chatting icon opens the transcript of that pair's conversation
 pub fn chatting_icon_on_hover(
+    mut commands: Commands,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut windows: Query<&mut Window>,
-    mut camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
-    mut chatting_icons_query: Query<(Entity, &Transform), With<ChattingIconButton>>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    chatting_icons_query: Query<(&Transform, &ChattingIconButton)>,
 ) {
-    let (camera, camera_transform) = camera_query.single_mut();
-    let window: Mut<'_, Window> = windows.single_mut();
+    if *game_state.get() != GameState::Playing {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (windows.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
 
     if mouse_button_input.just_pressed(MouseButton::Left) {
         if let Some(world_pos) = window
@@ -1054,13 +1020,19 @@ pub fn chatting_icon_on_hover(
             .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
             .map(|ray| ray.origin)
         {
-            for (_, transform) in chatting_icons_query.iter_mut() {
-                let icon_pos = transform.translation;
+            for (transform, chatting_icon_button) in chatting_icons_query.iter() {
+                let icon_pos = transform.translation.truncate();
                 let icon_size = Vec2::new(16.0, 16.0);
-                let collision = collide(world_pos, Vec2::ZERO, icon_pos, icon_size);
-
-                if collision.is_some() {
-                    println!("Chatting icon clicked!");
+                let hovered = Aabb2d::new(icon_pos, icon_size / 2.0)
+                    .intersects(&Aabb2d::new(world_pos.truncate(), Vec2::ZERO));
+
+                if hovered {
+                    commands.insert_resource(SideDialogueConversation::new(
+                        chatting_icon_button.entity_a_uuid.clone(),
+                        chatting_icon_button.entity_b_uuid.clone(),
+                    ));
+                    next_game_state.set(GameState::SideDialogue);
+                    return;
                 }
             }
         }
@@ -1068,11 +1040,12 @@ pub fn chatting_icon_on_hover(
 }
 
 fn spawn_emote(
-    mut commands: &mut Commands,
+    commands: &mut Commands,
     entities_query: &Query<(Entity, &mut Transform, &PlayerUniqueId), With<EtherPet>>,
     entity_id: String,
     emotion_index: usize,
-    emotion_icon_sheet: &Res<EmotionIconSheet>,
+    emotion_icon_sheet: &SpriteSheet,
+    emote_seconds: f32,
 ) {
     let pair_entities: Vec<(Entity, Transform, String)> = entities_query
         .iter()
@@ -1085,8 +1058,8 @@ fn spawn_emote(
     for (entity, transform, _entity_unique_id) in pair_entities {
         let emote_translation = transform.translation + Vec3::new(0.0, 17.5, 1000.0);
         let emote_entity: Entity = spawn_emote_sprite(
-            &mut commands,
-            &emotion_icon_sheet,
+            commands,
+            emotion_icon_sheet,
             emotion_index,
             emote_translation,
         );
@@ -1094,10 +1067,7 @@ fn spawn_emote(
         commands
             .entity(emote_entity)
             .insert(EmoteIcon {
-                emote_icon_despawn_timer: Timer::from_seconds(
-                    ENTITY_CONVO_DURATION * 0.5,
-                    TimerMode::Once,
-                ),
+                emote_icon_despawn_timer: Timer::from_seconds(emote_seconds, TimerMode::Once),
             })
             .insert(IconAnimation {
                 current_frame: 0,
@@ -1111,18 +1081,32 @@ pub fn emote_event(
     mut commands: Commands,
     mut emote_event_reader: EventReader<EmoteEvent>,
     entities_query: Query<(Entity, &mut Transform, &PlayerUniqueId), With<EtherPet>>,
-    emotion_icon_sheet: Res<EmotionIconSheet>,
+    moods_query: Query<(&PlayerUniqueId, &Mood), With<EtherPet>>,
+    sprite_sheets: Res<SpriteSheets>,
+    tuning: Res<FarmTuning>,
 ) {
-    for event in emote_event_reader.iter() {
+    let Some(emotion_icon_sheet) = sprite_sheets.get(EMOTION_ICON_SHEET) else {
+        return;
+    };
+    for event in emote_event_reader.read() {
         let entity_uuid = &event.entity_uuid;
-        let emotion_index = get_emotion_index(&event.emote);
+        // the icon shows how the pet feels now, the event's emote has
+        // already been folded into its mood
+        let emotion_index = moods_query
+            .iter()
+            .find(|(unique_id, _)| unique_id.uuid == *entity_uuid)
+            .map_or_else(
+                || get_emotion_index(&event.emote),
+                |(_, mood)| get_emotion_index(mood.dominant.as_str()),
+            );
 
         spawn_emote(
             &mut commands,
             &entities_query,
             entity_uuid.clone(),
             emotion_index,
-            &emotion_icon_sheet,
+            emotion_icon_sheet,
+            tuning.convo_duration * 0.5,
         );
     }
 }
@@ -1164,27 +1148,3 @@ fn remove_entity_from_conversation(
         .entities_in_conversation
         .retain(|&a, _| a != entity);
 }
-
-pub fn queue_pair_entity_conversation_request(// mut entity_pair_convo_state: ResMut<EntityPairConversationState>,
-    // mut pending_requests: ResMut<PendingConversationHttpRequests>,
-) {
-    // for (_, pair_convo_data) in entity_pair_convo_state
-    //     .pair_entities_in_conversation
-    //     .iter_mut()
-    // {
-    //     if !pair_convo_data.conversation_http_request_sent {
-    //         let body = json!({
-    //             "entity_a_uuid": pair_convo_data.entity_a_id,
-    //             "entity_b_uuid": pair_convo_data.entity_b_id,
-    //         });
-
-    //         pending_requests.requests.push(HttpRequest {
-    //             url: API_URL.to_string() + "/characters/converse",
-    //             method: HttpMethod::POST,
-    //             headers: HashMap::new(),
-    //             body,
-    //         });
-    //         pair_convo_data.conversation_http_request_sent = true;
-    //     }
-    // }
-}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+use crate::http_request::components::ConversationResponse;
+
+// affinity at or above which a pet goes looking for the other pet
+pub const SEEK_AFFINITY: f32 = 0.35;
+// affinity at or below which a pet keeps its distance
+pub const AVOID_AFFINITY: f32 = -0.35;
+// pets further apart than this don't pull or push each other around
+pub const RELATIONSHIP_SIGHT_DISTANCE: f32 = TILE_SIZE * 12.0;
+// how strongly relationships bend a roaming pet's random direction
+pub const SOCIAL_PULL_WEIGHT: f32 = 1.5;
+
+const AFFINITY_GAIN: f32 = 0.12;
+const FAMILIARITY_GAIN: f32 = 0.15;
+const TRUST_GAIN: f32 = 0.06;
+const TRUST_LOSS: f32 = 0.15;
+
+// How one pet feels about another. Relationships are directed, a pet can
+// adore someone who can't stand them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Relationship {
+    // -1.0 hates, 1.0 adores
+    pub affinity: f32,
+    // -1.0 expects hostility, 1.0 feels safe
+    pub trust: f32,
+    // 0.0 strangers, approaches 1.0 the more they talk
+    pub familiarity: f32,
+    pub conversations: u32,
+}
+
+impl Relationship {
+    // `own_emotion` is how this pet came out of the conversation, `other_emotion`
+    // how the other pet did
+    pub fn record_conversation(
+        &mut self,
+        own_emotion: BasicEmotions,
+        other_emotion: BasicEmotions,
+    ) {
+        // first impressions count the most, old friends are harder to sway
+        let impression_weight = 1.0 - self.familiarity * 0.5;
+
+        self.affinity = (self.affinity + own_emotion.valence() * AFFINITY_GAIN * impression_weight)
+            .clamp(-1.0, 1.0);
+
+        let trust_change = if other_emotion.is_hostile() {
+            -TRUST_LOSS
+        } else if other_emotion.is_positive() {
+            TRUST_GAIN
+        } else {
+            // opening up about sadness or fear still builds a little trust
+            TRUST_GAIN / 3.0
+        };
+        self.trust = (self.trust + trust_change).clamp(-1.0, 1.0);
+
+        self.familiarity += (1.0 - self.familiarity) * FAMILIARITY_GAIN;
+        self.conversations += 1;
+    }
+}
+
+// Every relationship between pets, keyed by (from, to) `PlayerUniqueId` uuids
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RelationshipGraph {
+    pub relationships: HashMap<(String, String), Relationship>,
+}
+
+impl RelationshipGraph {
+    pub fn get(&self, from: &str, to: &str) -> Option<&Relationship> {
+        self.relationships.get(&(from.to_string(), to.to_string()))
+    }
+
+    pub fn get_mut(&mut self, from: &str, to: &str) -> &mut Relationship {
+        self.relationships
+            .entry((from.to_string(), to.to_string()))
+            .or_default()
+    }
+
+    pub fn affinity(&self, from: &str, to: &str) -> f32 {
+        self.get(from, to)
+            .map_or(0.0, |relationship| relationship.affinity)
+    }
+
+    pub fn seeks(&self, from: &str, to: &str) -> bool {
+        self.affinity(from, to) >= SEEK_AFFINITY
+    }
+
+    pub fn avoids(&self, from: &str, to: &str) -> bool {
+        self.affinity(from, to) <= AVOID_AFFINITY
+    }
+
+    pub fn are_friends(&self, a: &str, b: &str) -> bool {
+        self.seeks(a, b) && self.seeks(b, a)
+    }
+
+    pub fn are_rivals(&self, a: &str, b: &str) -> bool {
+        self.avoids(a, b) && self.avoids(b, a)
+    }
+
+    // Direction a roaming pet at `position` is drawn in, toward pets it likes
+    // and away from pets it dislikes. Closer pets pull harder. Zero when
+    // nobody it cares about is in sight.
+    pub fn social_pull(&self, uuid: &str, position: Vec2, others: &[(String, Vec2)]) -> Vec2 {
+        let mut pull = Vec2::ZERO;
+        for (other_uuid, other_position) in others.iter() {
+            if other_uuid == uuid {
+                continue;
+            }
+
+            let offset = *other_position - position;
+            let distance = offset.length();
+            if distance == 0.0 || distance > RELATIONSHIP_SIGHT_DISTANCE {
+                continue;
+            }
+
+            let affinity = self.affinity(uuid, other_uuid);
+            if affinity > AVOID_AFFINITY && affinity < SEEK_AFFINITY {
+                continue;
+            }
+
+            let closeness = 1.0 - distance / RELATIONSHIP_SIGHT_DISTANCE;
+            pull += offset / distance * affinity * closeness;
+        }
+        pull
+    }
+}
+
+// A response arrives when a conversation starts, but it only counts once the
+// pair has talked it through. Responses wait here until then, keyed by the
+// pair's uuids in sorted order.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct UnfinishedConversations {
+    pub responses: HashMap<(String, String), ConversationResponse>,
+}
+
+impl UnfinishedConversations {
+    fn pair_key(uuid_a: &str, uuid_b: &str) -> (String, String) {
+        if uuid_a <= uuid_b {
+            (uuid_a.to_string(), uuid_b.to_string())
+        } else {
+            (uuid_b.to_string(), uuid_a.to_string())
+        }
+    }
+
+    // a pair that's interrupted has its response replaced by the next one
+    pub fn insert(&mut self, response: ConversationResponse) {
+        let key = Self::pair_key(&response.entity_a_uuid, &response.entity_b_uuid);
+        self.responses.insert(key, response);
+    }
+
+    pub fn take(&mut self, uuid_a: &str, uuid_b: &str) -> Option<ConversationResponse> {
+        self.responses.remove(&Self::pair_key(uuid_a, uuid_b))
+    }
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+
+mod systems;
+use systems::update_relationships_from_conversations;
+
+pub mod components;
+use components::{RelationshipGraph, UnfinishedConversations};
+
+pub struct RelationshipPlugin;
+
+impl Plugin for RelationshipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RelationshipGraph>()
+            .init_resource::<UnfinishedConversations>()
+            .add_systems(
+                FixedUpdate,
+                update_relationships_from_conversations.in_set(FarmSet::Relationships),
+            );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::player::components::{BasicEmotions, EtherPet, PlayerData, PlayerUniqueId};
+use crate::gameplay::relationship::components::{RelationshipGraph, UnfinishedConversations};
+use crate::http_request::components::ConversationResponse;
+
+// events
+use crate::gameplay::events::ConversationFinishedEvent;
+use crate::http_request::events::ConversationHistoryEvent;
+
+// The emote a pet walked away with, falling back to its base emotion when
+// the response didn't include a usable one
+fn emotion_after_conversation(
+    response: &ConversationResponse,
+    uuid: &str,
+    base_emotions: &HashMap<String, BasicEmotions>,
+) -> Option<BasicEmotions> {
+    response
+        .emotes
+        .iter()
+        .filter(|emote| emote.entity_uuid == uuid)
+        .find_map(|emote| BasicEmotions::parse(&emote.emote))
+        .or_else(|| base_emotions.get(uuid).copied())
+}
+
+pub fn update_relationships_from_conversations(
+    mut conversation_history_event_reader: EventReader<ConversationHistoryEvent>,
+    mut conversation_finished_event_reader: EventReader<ConversationFinishedEvent>,
+    mut unfinished_conversations: ResMut<UnfinishedConversations>,
+    mut relationship_graph: ResMut<RelationshipGraph>,
+    pets_query: Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+) {
+    for event in conversation_history_event_reader.read() {
+        unfinished_conversations.insert(event.response.clone());
+    }
+
+    let base_emotions: HashMap<String, BasicEmotions> = pets_query
+        .iter()
+        .map(|(unique_id, player_data)| (unique_id.uuid.clone(), player_data.emotion))
+        .collect();
+
+    for event in conversation_finished_event_reader.read() {
+        let Ok([(unique_id_a, _), (unique_id_b, _)]) =
+            pets_query.get_many([event.entity_a, event.entity_b])
+        else {
+            continue;
+        };
+        let Some(response) = unfinished_conversations.take(&unique_id_a.uuid, &unique_id_b.uuid)
+        else {
+            continue;
+        };
+        let uuid_a = response.entity_a_uuid.as_str();
+        let uuid_b = response.entity_b_uuid.as_str();
+
+        let (Some(emotion_a), Some(emotion_b)) = (
+            emotion_after_conversation(&response, uuid_a, &base_emotions),
+            emotion_after_conversation(&response, uuid_b, &base_emotions),
+        ) else {
+            continue;
+        };
+
+        let were_friends = relationship_graph.are_friends(uuid_a, uuid_b);
+        let were_rivals = relationship_graph.are_rivals(uuid_a, uuid_b);
+
+        relationship_graph
+            .get_mut(uuid_a, uuid_b)
+            .record_conversation(emotion_a, emotion_b);
+        relationship_graph
+            .get_mut(uuid_b, uuid_a)
+            .record_conversation(emotion_b, emotion_a);
+
+        if !were_friends && relationship_graph.are_friends(uuid_a, uuid_b) {
+            info!("{} and {} became friends", uuid_a, uuid_b);
+        }
+        if !were_rivals && relationship_graph.are_rivals(uuid_a, uuid_b) {
+            info!("{} and {} became rivals", uuid_a, uuid_b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::components::ConversationEmote;
+
+    fn spawn_pet(app: &mut App, uuid: &str) -> Entity {
+        app.world
+            .spawn((
+                EtherPet,
+                PlayerUniqueId {
+                    uuid: uuid.to_string(),
+                },
+                PlayerData {
+                    name: uuid.to_string(),
+                    attack_dmg: 0,
+                    speed: 1.0,
+                    player_id: uuid.to_string(),
+                    emotion: BasicEmotions::Happiness,
+                },
+            ))
+            .id()
+    }
+
+    fn conversation_between(uuid_a: &str, uuid_b: &str) -> ConversationHistoryEvent {
+        ConversationHistoryEvent {
+            response: ConversationResponse {
+                entity_a_uuid: uuid_a.to_string(),
+                entity_b_uuid: uuid_b.to_string(),
+                conversation: Vec::new(),
+                emotes: vec![ConversationEmote {
+                    entity_uuid: uuid_b.to_string(),
+                    emote: "excitement".to_string(),
+                }],
+            },
+        }
+    }
+
+    fn relationships_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.init_resource::<RelationshipGraph>()
+            .init_resource::<UnfinishedConversations>()
+            .add_event::<ConversationHistoryEvent>()
+            .add_event::<ConversationFinishedEvent>()
+            .add_systems(Update, update_relationships_from_conversations);
+        let entity_a = spawn_pet(&mut app, "a");
+        let entity_b = spawn_pet(&mut app, "b");
+        (app, entity_a, entity_b)
+    }
+
+    fn conversations(app: &App, from: &str, to: &str) -> u32 {
+        app.world
+            .resource::<RelationshipGraph>()
+            .get(from, to)
+            .map_or(0, |relationship| relationship.conversations)
+    }
+
+    #[test]
+    fn relationship_changes_once_the_conversation_finishes() {
+        let (mut app, entity_a, entity_b) = relationships_app();
+
+        app.world.send_event(conversation_between("a", "b"));
+        app.update();
+        assert_eq!(conversations(&app, "a", "b"), 0);
+
+        // the finished pair can come in either order
+        app.world.send_event(ConversationFinishedEvent {
+            entity_a: entity_b,
+            entity_b: entity_a,
+        });
+        app.update();
+        assert_eq!(conversations(&app, "a", "b"), 1);
+        assert_eq!(conversations(&app, "b", "a"), 1);
+        let relationship = app
+            .world
+            .resource::<RelationshipGraph>()
+            .get("a", "b")
+            .unwrap();
+        assert!(relationship.familiarity > 0.0);
+
+        // and the same response isn't counted twice
+        app.world
+            .send_event(ConversationFinishedEvent { entity_a, entity_b });
+        app.update();
+        assert_eq!(conversations(&app, "a", "b"), 1);
+    }
+
+    #[test]
+    fn interrupted_conversation_leaves_no_trace() {
+        let (mut app, entity_a, _) = relationships_app();
+        let entity_c = spawn_pet(&mut app, "c");
+
+        app.world.send_event(conversation_between("a", "b"));
+        app.world.send_event(conversation_between("a", "c"));
+        app.update();
+        app.world.send_event(ConversationFinishedEvent {
+            entity_a,
+            entity_b: entity_c,
+        });
+        app.update();
+
+        assert_eq!(conversations(&app, "a", "c"), 1);
+        assert_eq!(conversations(&app, "a", "b"), 0);
+        assert!(app
+            .world
+            .resource::<RelationshipGraph>()
+            .get("b", "a")
+            .is_none());
+    }
+}
@@ -0,0 +1,101 @@
+use rand::RngCore;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+// set to replay a farm, otherwise every run gets a fresh seed
+pub const SEED_ENV: &str = "GOSSIP_SEED";
+
+// FNV-1a, used instead of std's hasher so anything derived from it is stable
+// across runs and toolchains
+pub fn stable_hash(parts: &[&str], salt: u64) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325 ^ salt;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+// The one source of randomness for the simulation. The same seed and the
+// same inputs play out the same farm history. Its state goes into save files,
+// so a loaded farm carries on drawing where the saved one left off.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+pub struct SimRng {
+    seed: u64,
+    rng: ChaCha12Rng,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // An independent stream for one entity, so what it draws doesn't depend
+    // on how many numbers everything else drew first. Key it by something
+    // stable across runs like a pet's uuid, not its `Entity`.
+    pub fn stream(&self, key: &str) -> EntityRng {
+        EntityRng(ChaCha12Rng::seed_from_u64(stable_hash(&[key], self.seed)))
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        let seed = std::env::var(SEED_ENV)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(|| rand::thread_rng().gen());
+        info!("Simulation seed {}, set {} to replay it", seed, SEED_ENV);
+        Self::new(seed)
+    }
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// A pet's or cow's own stream from `SimRng::stream`
+#[derive(Component, Serialize, Deserialize, Debug, Clone)]
+pub struct EntityRng(ChaCha12Rng);
+
+impl RngCore for EntityRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
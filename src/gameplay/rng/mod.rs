@@ -0,0 +1,12 @@
+use crate::prelude::*;
+
+pub mod components;
+use components::SimRng;
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimRng>();
+    }
+}
@@ -0,0 +1,401 @@
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+use crate::gameplay::clock::components::GameClock;
+use crate::gameplay::components::{InteractionTimers, Roaming};
+use crate::gameplay::gossip::components::{Rumor, RumorLog, RumorTransfer};
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::needs::components::Needs;
+use crate::gameplay::player::components::{BasicEmotions, EntityPairConversationState, Quadrants};
+use crate::gameplay::relationship::components::{
+    Relationship, RelationshipGraph, UnfinishedConversations,
+};
+use crate::gameplay::rng::components::{EntityRng, SimRng};
+use crate::http_request::components::{ConversationHistoryResource, ConversationResponse};
+
+// bump whenever the layout of SaveFile changes, older saves are rejected
+pub const SAVE_VERSION: u32 = 7;
+pub const DEFAULT_SAVE_PATH: &str = "saves/gossip_farm_save.json";
+
+#[derive(Resource, Debug, Clone)]
+pub struct SaveSettings {
+    pub path: PathBuf,
+}
+
+impl Default for SaveSettings {
+    fn default() -> Self {
+        Self {
+            path: std::env::var("GOSSIP_SAVE")
+                .unwrap_or_else(|_| DEFAULT_SAVE_PATH.to_string())
+                .into(),
+        }
+    }
+}
+
+// The simulation resources that go into a save file alongside the pets
+#[derive(SystemParam)]
+pub struct SimulationResources<'w> {
+    pub relationship_graph: ResMut<'w, RelationshipGraph>,
+    pub unfinished_conversations: ResMut<'w, UnfinishedConversations>,
+    pub rumor_log: ResMut<'w, RumorLog>,
+    pub entity_pair_convo_state: ResMut<'w, EntityPairConversationState>,
+    pub conversation_history: ResMut<'w, ConversationHistoryResource>,
+    pub clock: ResMut<'w, GameClock>,
+    pub sim_rng: ResMut<'w, SimRng>,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "could not access save file: {}", error),
+            SaveError::Json(error) => write!(f, "could not parse save file: {}", error),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "save file version {} is not supported, expected {}",
+                version, SAVE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimerSave {
+    pub duration: f32,
+    pub elapsed: f32,
+    pub repeating: bool,
+}
+
+impl From<&Timer> for TimerSave {
+    fn from(timer: &Timer) -> Self {
+        Self {
+            duration: timer.duration().as_secs_f32(),
+            elapsed: timer.elapsed().as_secs_f32(),
+            repeating: timer.mode() == TimerMode::Repeating,
+        }
+    }
+}
+
+impl TimerSave {
+    pub fn to_timer(&self) -> Timer {
+        let mode = if self.repeating {
+            TimerMode::Repeating
+        } else {
+            TimerMode::Once
+        };
+        let mut timer = Timer::from_seconds(self.duration, mode);
+        timer.set_elapsed(Duration::from_secs_f32(self.elapsed));
+        timer
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoamingSave {
+    pub roam_speed: f32,
+    pub roam_max_time: f32,
+    pub idle_max_time: f32,
+}
+
+impl From<&Roaming> for RoamingSave {
+    fn from(roaming: &Roaming) -> Self {
+        Self {
+            roam_speed: roaming.roam_speed,
+            roam_max_time: roaming.roam_max_time,
+            idle_max_time: roaming.idle_max_time,
+        }
+    }
+}
+
+impl RoamingSave {
+    pub fn to_roaming(&self) -> Roaming {
+        Roaming {
+            roam_speed: self.roam_speed,
+            roam_max_time: self.roam_max_time,
+            idle_max_time: self.idle_max_time,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InteractionTimersSave {
+    pub prox_chat_cooldown_timer: TimerSave,
+    pub can_prox_chat: bool,
+    pub prox_chat_timer: TimerSave,
+    pub prox_chat_active: bool,
+}
+
+impl From<&InteractionTimers> for InteractionTimersSave {
+    fn from(interaction_timers: &InteractionTimers) -> Self {
+        Self {
+            prox_chat_cooldown_timer: (&interaction_timers.prox_chat_cooldown_timer).into(),
+            can_prox_chat: interaction_timers.can_prox_chat,
+            prox_chat_timer: (&interaction_timers.prox_chat_timer).into(),
+            prox_chat_active: interaction_timers.prox_chat_active,
+        }
+    }
+}
+
+impl InteractionTimersSave {
+    pub fn to_interaction_timers(&self) -> InteractionTimers {
+        InteractionTimers {
+            prox_chat_cooldown_timer: self.prox_chat_cooldown_timer.to_timer(),
+            can_prox_chat: self.can_prox_chat,
+            prox_chat_timer: self.prox_chat_timer.to_timer(),
+            prox_chat_active: self.prox_chat_active,
+        }
+    }
+}
+
+// Pets are matched back up with the spawned entities by uuid
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PetSave {
+    pub uuid: String,
+    pub translation: [f32; 3],
+    pub emotion: BasicEmotions,
+    pub mood: Mood,
+    pub needs: Needs,
+    pub roaming: RoamingSave,
+    pub interaction_timers: InteractionTimersSave,
+    pub rumors: Vec<Rumor>,
+    pub rng: EntityRng,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RelationshipSave {
+    pub from_uuid: String,
+    pub to_uuid: String,
+    pub relationship: Relationship,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RumorLogSave {
+    pub next_rumor_id: u64,
+    pub origins: Vec<Rumor>,
+    pub transfers: Vec<RumorTransfer>,
+}
+
+// a pair of pets that were walking up to each other or chatting
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationSave {
+    pub entity_a_uuid: String,
+    pub entity_b_uuid: String,
+    pub entity_a_pos: [f32; 3],
+    pub entity_b_pos: [f32; 3],
+    pub quadrant: Quadrants,
+    pub conversation_http_request_sent: bool,
+    pub path: Vec<[f32; 2]>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveFile {
+    pub version: u32,
+    pub clock: GameClock,
+    pub rng: SimRng,
+    pub pets: Vec<PetSave>,
+    pub relationships: Vec<RelationshipSave>,
+    pub rumor_log: RumorLogSave,
+    pub conversations: Vec<ConversationSave>,
+    // responses of conversations still under way, applied when they finish
+    pub unfinished_conversations: Vec<ConversationResponse>,
+    pub conversation_history: Vec<ConversationResponse>,
+}
+
+impl SaveFile {
+    pub fn write(&self, path: &std::path::Path) -> Result<(), SaveError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(SaveError::Io)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(SaveError::Json)?;
+        std::fs::write(path, json).map_err(SaveError::Io)
+    }
+
+    pub fn read(path: &std::path::Path) -> Result<Self, SaveError> {
+        let json = std::fs::read_to_string(path).map_err(SaveError::Io)?;
+
+        // check the version before the layout, so an old save reports the
+        // version mismatch instead of a missing field
+        let value: serde_json::Value = serde_json::from_str(&json).map_err(SaveError::Json)?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0) as u32;
+        if version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+
+        serde_json::from_value(value).map_err(SaveError::Json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::player::components::BasicEmotions;
+    use crate::http_request::components::{ConversationEmote, ConversationLine};
+
+    fn save_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("gossip_save_{}_{}.json", name, std::process::id()))
+    }
+
+    fn response(uuid_a: &str, uuid_b: &str) -> ConversationResponse {
+        ConversationResponse {
+            entity_a_uuid: uuid_a.to_string(),
+            entity_b_uuid: uuid_b.to_string(),
+            conversation: vec![ConversationLine {
+                speaker_uuid: uuid_a.to_string(),
+                text: "Lovely weather".to_string(),
+            }],
+            emotes: vec![ConversationEmote {
+                entity_uuid: uuid_b.to_string(),
+                emote: "Happiness".to_string(),
+            }],
+        }
+    }
+
+    fn save_file() -> SaveFile {
+        let mut rumor_log = RumorLog::default();
+        let rumor = rumor_log.start_rumor("a", "b", "Pip", BasicEmotions::Fear);
+        let mut relationship = Relationship::default();
+        relationship.record_conversation(BasicEmotions::Happiness, BasicEmotions::Excitement);
+
+        let mut rng = SimRng::new(5);
+        rng.gen::<u64>();
+        let mut pet_rng = rng.stream("a");
+        pet_rng.gen::<u64>();
+
+        SaveFile {
+            version: SAVE_VERSION,
+            rng,
+            clock: GameClock {
+                day: 3,
+                hour: 17.5,
+                ..default()
+            },
+            pets: vec![PetSave {
+                uuid: "a".to_string(),
+                translation: [16.0, 32.0, 1.0],
+                emotion: BasicEmotions::Sadness,
+                mood: Mood::new(BasicEmotions::Sadness, 0.4),
+                needs: Needs::default(),
+                roaming: RoamingSave {
+                    roam_speed: 30.0,
+                    roam_max_time: 6.0,
+                    idle_max_time: 4.0,
+                },
+                interaction_timers: InteractionTimersSave {
+                    prox_chat_cooldown_timer: (&Timer::from_seconds(15.0, TimerMode::Once)).into(),
+                    can_prox_chat: false,
+                    prox_chat_timer: (&Timer::from_seconds(5.0, TimerMode::Once)).into(),
+                    prox_chat_active: true,
+                },
+                rumors: vec![rumor.clone()],
+                rng: pet_rng,
+            }],
+            relationships: vec![RelationshipSave {
+                from_uuid: "a".to_string(),
+                to_uuid: "b".to_string(),
+                relationship,
+            }],
+            rumor_log: RumorLogSave {
+                next_rumor_id: rumor_log.next_rumor_id,
+                origins: vec![rumor],
+                transfers: Vec::new(),
+            },
+            conversations: vec![ConversationSave {
+                entity_a_uuid: "a".to_string(),
+                entity_b_uuid: "b".to_string(),
+                entity_a_pos: [16.0, 32.0, 1.0],
+                entity_b_pos: [64.0, 32.0, 1.0],
+                quadrant: Quadrants::TopLeft,
+                conversation_http_request_sent: true,
+                path: vec![[48.0, 32.0], [32.0, 32.0]],
+            }],
+            unfinished_conversations: vec![response("a", "b")],
+            conversation_history: vec![response("b", "c")],
+        }
+    }
+
+    #[test]
+    fn save_file_reads_back_what_was_written() {
+        let path = save_path("round_trip");
+        let written = save_file();
+        written.write(&path).unwrap();
+        let read = SaveFile::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(read.clock, written.clock);
+        assert_eq!(read.pets[0].mood, written.pets[0].mood);
+        // both rngs carry on from where they were saved
+        assert_eq!(read.rng.seed(), 5);
+        assert_eq!(
+            read.rng.clone().gen::<u64>(),
+            written.rng.clone().gen::<u64>()
+        );
+        assert_eq!(
+            read.pets[0].rng.clone().gen::<u64>(),
+            written.pets[0].rng.clone().gen::<u64>()
+        );
+        assert_eq!(read.pets[0].rumors, written.pets[0].rumors);
+        assert_eq!(
+            read.pets[0]
+                .interaction_timers
+                .to_interaction_timers()
+                .prox_chat_timer,
+            written.pets[0]
+                .interaction_timers
+                .to_interaction_timers()
+                .prox_chat_timer
+        );
+        assert_eq!(
+            read.relationships[0].relationship,
+            written.relationships[0].relationship
+        );
+        assert_eq!(read.rumor_log.origins, written.rumor_log.origins);
+        assert_eq!(read.conversations[0].path, written.conversations[0].path);
+        assert_eq!(
+            read.unfinished_conversations[0].emotes[0].emote,
+            "Happiness"
+        );
+        assert_eq!(
+            serde_json::to_value(&read).unwrap(),
+            serde_json::to_value(&written).unwrap()
+        );
+    }
+
+    #[test]
+    fn older_save_is_rejected_by_version() {
+        let path = save_path("old_version");
+        let mut old = serde_json::to_value(save_file()).unwrap();
+        old["version"] = (SAVE_VERSION - 1).into();
+        // older layouts were missing fields the current one has
+        old.as_object_mut()
+            .unwrap()
+            .remove("unfinished_conversations");
+        std::fs::write(&path, old.to_string()).unwrap();
+
+        let result = SaveFile::read(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(
+            result,
+            Err(SaveError::UnsupportedVersion(version)) if version == SAVE_VERSION - 1
+        ));
+    }
+
+    #[test]
+    fn missing_save_is_an_io_error() {
+        let result = SaveFile::read(&save_path("missing"));
+        assert!(matches!(result, Err(SaveError::Io(_))));
+    }
+}
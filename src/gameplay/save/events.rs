@@ -0,0 +1,7 @@
+use crate::prelude::*;
+
+#[derive(Event, Debug)]
+pub struct SaveGameEvent;
+
+#[derive(Event, Debug)]
+pub struct LoadGameEvent;
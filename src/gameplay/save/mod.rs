@@ -0,0 +1,33 @@
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::state::components::GameState;
+
+mod systems;
+use systems::{load_game, save_game, save_game_hotkeys};
+
+pub mod components;
+use components::SaveSettings;
+
+pub mod events;
+use events::{LoadGameEvent, SaveGameEvent};
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSettings>()
+            .add_event::<SaveGameEvent>()
+            .add_event::<LoadGameEvent>()
+            .add_systems(
+                Update,
+                (
+                    save_game_hotkeys.run_if(in_state(GameState::Pause)),
+                    save_game,
+                    load_game,
+                )
+                    .chain()
+                    .in_set(FarmSet::Save),
+            );
+    }
+}
@@ -0,0 +1,535 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::behaviour::components::ActiveBehaviour;
+use crate::gameplay::clock::components::GameClock;
+use crate::gameplay::components::{
+    ChattingIconButton, EntityActionState, GenericActions, InteractionTimers, Roaming,
+};
+use crate::gameplay::gossip::components::{RumorLog, RumorMemory};
+use crate::gameplay::interpolation::components::InterpolatedTranslation;
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::needs::components::Needs;
+use crate::gameplay::player::components::{
+    EntityPairConversationState, EtherPet, PairEntityConversationData, PlayerData, PlayerUniqueId,
+};
+use crate::gameplay::relationship::components::{RelationshipGraph, UnfinishedConversations};
+use crate::gameplay::rng::components::{EntityRng, SimRng};
+use crate::gameplay::save::components::{
+    ConversationSave, PetSave, RelationshipSave, RumorLogSave, SaveFile, SaveSettings,
+    SimulationResources, SAVE_VERSION,
+};
+use crate::http_request::components::ConversationHistoryResource;
+
+// events
+use crate::gameplay::save::events::{LoadGameEvent, SaveGameEvent};
+
+pub fn save_game_hotkeys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut save_game_event_writer: EventWriter<SaveGameEvent>,
+    mut load_game_event_writer: EventWriter<LoadGameEvent>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F5) {
+        save_game_event_writer.send(SaveGameEvent);
+    }
+    if keyboard_input.just_pressed(KeyCode::F9) {
+        load_game_event_writer.send(LoadGameEvent);
+    }
+}
+
+pub fn save_game(
+    mut save_game_event_reader: EventReader<SaveGameEvent>,
+    save_settings: Res<SaveSettings>,
+    clock: Res<GameClock>,
+    sim_rng: Res<SimRng>,
+    relationship_graph: Res<RelationshipGraph>,
+    unfinished_conversations: Res<UnfinishedConversations>,
+    rumor_log: Res<RumorLog>,
+    entity_pair_convo_state: Res<EntityPairConversationState>,
+    conversation_history: Res<ConversationHistoryResource>,
+    pets_query: Query<
+        (
+            &PlayerUniqueId,
+            &InterpolatedTranslation,
+            &PlayerData,
+            &Mood,
+            &Needs,
+            &Roaming,
+            &InteractionTimers,
+            &RumorMemory,
+            &EntityRng,
+        ),
+        With<EtherPet>,
+    >,
+) {
+    // any number of save requests in a frame write the same file
+    if save_game_event_reader.read().count() == 0 {
+        return;
+    }
+
+    let pets = pets_query
+        .iter()
+        .map(
+            |(
+                unique_id,
+                interpolated,
+                player_data,
+                mood,
+                needs,
+                roaming,
+                interaction_timers,
+                rumor_memory,
+                rng,
+            )| {
+                PetSave {
+                    uuid: unique_id.uuid.clone(),
+                    // where the last tick left it, not where the frame drew it
+                    translation: interpolated.current.to_array(),
+                    emotion: player_data.emotion,
+                    mood: mood.clone(),
+                    needs: needs.clone(),
+                    roaming: roaming.into(),
+                    interaction_timers: interaction_timers.into(),
+                    rumors: rumor_memory.rumors.iter().cloned().collect(),
+                    rng: rng.clone(),
+                }
+            },
+        )
+        .collect();
+
+    let mut relationships: Vec<RelationshipSave> = relationship_graph
+        .relationships
+        .iter()
+        .map(|((from_uuid, to_uuid), relationship)| RelationshipSave {
+            from_uuid: from_uuid.clone(),
+            to_uuid: to_uuid.clone(),
+            relationship: relationship.clone(),
+        })
+        .collect();
+    relationships.sort_by(|a, b| (&a.from_uuid, &a.to_uuid).cmp(&(&b.from_uuid, &b.to_uuid)));
+
+    let mut unfinished: Vec<_> = unfinished_conversations
+        .responses
+        .values()
+        .cloned()
+        .collect();
+    unfinished.sort_by(|a, b| {
+        (&a.entity_a_uuid, &a.entity_b_uuid).cmp(&(&b.entity_a_uuid, &b.entity_b_uuid))
+    });
+
+    let mut origins: Vec<_> = rumor_log.origins.values().cloned().collect();
+    origins.sort_by_key(|rumor| rumor.id);
+
+    let conversations = entity_pair_convo_state
+        .pair_entities_in_conversation
+        .values()
+        .map(|pair_convo_data| ConversationSave {
+            entity_a_uuid: pair_convo_data.entity_a_id.clone(),
+            entity_b_uuid: pair_convo_data.entity_b_id.clone(),
+            entity_a_pos: pair_convo_data.entity_a_pos.to_array(),
+            entity_b_pos: pair_convo_data.entity_b_pos.to_array(),
+            quadrant: pair_convo_data.quadrant.clone(),
+            conversation_http_request_sent: pair_convo_data.conversation_http_request_sent,
+            path: pair_convo_data
+                .path
+                .iter()
+                .map(|waypoint| waypoint.to_array())
+                .collect(),
+        })
+        .collect();
+
+    let save_file = SaveFile {
+        version: SAVE_VERSION,
+        clock: clock.clone(),
+        rng: sim_rng.clone(),
+        pets,
+        relationships,
+        rumor_log: RumorLogSave {
+            next_rumor_id: rumor_log.next_rumor_id,
+            origins,
+            transfers: rumor_log.transfers.clone(),
+        },
+        conversations,
+        unfinished_conversations: unfinished,
+        conversation_history: conversation_history.conversations.clone(),
+    };
+
+    match save_file.write(&save_settings.path) {
+        Ok(()) => info!("Saved the farm to {:?}", save_settings.path),
+        Err(error) => error!("Failed to save the farm: {}", error),
+    }
+}
+
+pub fn load_game(
+    mut commands: Commands,
+    mut load_game_event_reader: EventReader<LoadGameEvent>,
+    save_settings: Res<SaveSettings>,
+    mut simulation: SimulationResources,
+    mut pets_query: Query<
+        (
+            Entity,
+            &PlayerUniqueId,
+            &mut Transform,
+            &mut InterpolatedTranslation,
+            &mut PlayerData,
+            &mut Mood,
+            &mut Needs,
+            &mut ActiveBehaviour,
+            &mut EntityActionState,
+            &mut Roaming,
+            &mut InteractionTimers,
+            &mut RumorMemory,
+            &mut EntityRng,
+        ),
+        With<EtherPet>,
+    >,
+    chatting_icons_query: Query<Entity, With<ChattingIconButton>>,
+) {
+    if load_game_event_reader.read().count() == 0 {
+        return;
+    }
+
+    let save_file = match SaveFile::read(&save_settings.path) {
+        Ok(save_file) => save_file,
+        Err(error) => {
+            error!("Failed to load {:?}: {}", save_settings.path, error);
+            return;
+        }
+    };
+
+    let mut entities_by_uuid = HashMap::new();
+    for (
+        entity,
+        unique_id,
+        mut transform,
+        mut interpolated,
+        mut player_data,
+        mut mood,
+        mut needs,
+        mut active_behaviour,
+        mut action_state,
+        mut roaming,
+        mut interaction_timers,
+        mut rumor_memory,
+        mut rng,
+    ) in pets_query.iter_mut()
+    {
+        entities_by_uuid.insert(unique_id.uuid.clone(), entity);
+
+        let Some(pet) = save_file.pets.iter().find(|pet| pet.uuid == unique_id.uuid) else {
+            warn!("Save file has no entry for pet {}", unique_id.uuid);
+            continue;
+        };
+
+        transform.translation = Vec3::from_array(pet.translation);
+        // put straight there rather than sliding over from the old spot
+        *interpolated = InterpolatedTranslation::new(transform.translation);
+        player_data.emotion = pet.emotion;
+        *mood = pet.mood.clone();
+        *needs = pet.needs.clone();
+        // pets decide what to do next from wherever they were put
+        *active_behaviour = ActiveBehaviour::default();
+        action_state.action = GenericActions::Idle;
+        *roaming = pet.roaming.to_roaming();
+        *interaction_timers = pet.interaction_timers.to_interaction_timers();
+        rumor_memory.rumors = pet.rumors.iter().cloned().collect();
+        *rng = pet.rng.clone();
+    }
+
+    *simulation.clock = save_file.clock;
+    *simulation.sim_rng = save_file.rng;
+    simulation.relationship_graph.relationships = save_file
+        .relationships
+        .into_iter()
+        .map(|saved| ((saved.from_uuid, saved.to_uuid), saved.relationship))
+        .collect();
+
+    *simulation.unfinished_conversations = UnfinishedConversations::default();
+    for response in save_file.unfinished_conversations {
+        simulation.unfinished_conversations.insert(response);
+    }
+
+    *simulation.rumor_log = RumorLog {
+        next_rumor_id: save_file.rumor_log.next_rumor_id,
+        origins: save_file
+            .rumor_log
+            .origins
+            .into_iter()
+            .map(|rumor| (rumor.id, rumor))
+            .collect(),
+        transfers: save_file.rumor_log.transfers,
+    };
+
+    simulation.conversation_history.conversations = save_file.conversation_history;
+    simulation.conversation_history.entities_to_emote.clear();
+
+    // chatting icons belong to the conversations being replaced, the restored
+    // ones get fresh icons from the spawner
+    for chatting_icon_entity in chatting_icons_query.iter() {
+        commands.entity(chatting_icon_entity).despawn();
+    }
+
+    *simulation.entity_pair_convo_state = EntityPairConversationState::default();
+    for conversation in save_file.conversations {
+        let (Some(&entity_a), Some(&entity_b)) = (
+            entities_by_uuid.get(&conversation.entity_a_uuid),
+            entities_by_uuid.get(&conversation.entity_b_uuid),
+        ) else {
+            continue;
+        };
+
+        let entity_pair_convo_state = &mut simulation.entity_pair_convo_state;
+        entity_pair_convo_state
+            .pair_entities_in_conversation
+            .insert(
+                (entity_a, entity_b),
+                PairEntityConversationData {
+                    entity_a,
+                    entity_b,
+                    entity_a_pos: Vec3::from_array(conversation.entity_a_pos),
+                    entity_b_pos: Vec3::from_array(conversation.entity_b_pos),
+                    entity_a_id: conversation.entity_a_uuid,
+                    entity_b_id: conversation.entity_b_uuid,
+                    quadrant: conversation.quadrant,
+                    chatting_icon_spawned: false,
+                    conversation_http_request_sent: conversation.conversation_http_request_sent,
+                    path: conversation
+                        .path
+                        .into_iter()
+                        .map(Vec2::from_array)
+                        .collect(),
+                },
+            );
+        entity_pair_convo_state
+            .entities_in_conversation
+            .insert(entity_a, true);
+        entity_pair_convo_state
+            .entities_in_conversation
+            .insert(entity_b, true);
+    }
+
+    info!("Loaded the farm from {:?}", save_settings.path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::player::components::{BasicEmotions, Quadrants};
+    use crate::gameplay::relationship::components::Relationship;
+    use crate::http_request::components::ConversationResponse;
+
+    fn save_app(name: &str) -> App {
+        let mut app = App::new();
+        app.insert_resource(SaveSettings {
+            path: std::env::temp_dir().join(format!(
+                "gossip_save_{}_{}.json",
+                name,
+                std::process::id()
+            )),
+        })
+        .insert_resource(SimRng::new(5))
+        .init_resource::<GameClock>()
+        .init_resource::<RelationshipGraph>()
+        .init_resource::<UnfinishedConversations>()
+        .init_resource::<RumorLog>()
+        .init_resource::<EntityPairConversationState>()
+        .init_resource::<ConversationHistoryResource>()
+        .add_event::<SaveGameEvent>()
+        .add_event::<LoadGameEvent>()
+        .add_systems(Update, (save_game, load_game).chain());
+        app
+    }
+
+    fn spawn_pet(app: &mut App, uuid: &str, translation: Vec3) -> Entity {
+        app.world
+            .spawn((
+                EtherPet,
+                PlayerUniqueId {
+                    uuid: uuid.to_string(),
+                },
+                Transform::from_translation(translation),
+                InterpolatedTranslation::new(translation),
+                PlayerData {
+                    name: uuid.to_string(),
+                    attack_dmg: 0,
+                    speed: 1.0,
+                    player_id: uuid.to_string(),
+                    emotion: BasicEmotions::Happiness,
+                },
+                Mood::new(BasicEmotions::Happiness, 0.4),
+                Needs::default(),
+                ActiveBehaviour::default(),
+                EntityActionState {
+                    action: GenericActions::Walk,
+                },
+                Roaming {
+                    roam_speed: 30.0,
+                    roam_max_time: 6.0,
+                    idle_max_time: 4.0,
+                },
+                InteractionTimers {
+                    prox_chat_cooldown_timer: Timer::from_seconds(15.0, TimerMode::Once),
+                    can_prox_chat: true,
+                    prox_chat_timer: Timer::from_seconds(5.0, TimerMode::Once),
+                    prox_chat_active: false,
+                },
+                RumorMemory::default(),
+                SimRng::new(5).stream(uuid),
+            ))
+            .id()
+    }
+
+    fn pet_translation(app: &App, entity: Entity) -> Vec3 {
+        app.world.get::<Transform>(entity).unwrap().translation
+    }
+
+    #[test]
+    fn loading_restores_the_saved_farm() {
+        let mut app = save_app("systems_round_trip");
+        let entity_a = spawn_pet(&mut app, "a", Vec3::new(16.0, 32.0, 1.0));
+        let entity_b = spawn_pet(&mut app, "b", Vec3::new(64.0, 32.0, 1.0));
+
+        // the farm as it was saved
+        app.world.resource_mut::<GameClock>().day = 3;
+        let rumor =
+            app.world
+                .resource_mut::<RumorLog>()
+                .start_rumor("a", "b", "b", BasicEmotions::Fear);
+        app.world
+            .get_mut::<RumorMemory>(entity_a)
+            .unwrap()
+            .remember(rumor.clone(), 12);
+        app.world.get_mut::<Mood>(entity_a).unwrap().dominant = BasicEmotions::Sadness;
+        let mut relationship = Relationship::default();
+        relationship.record_conversation(BasicEmotions::Happiness, BasicEmotions::Happiness);
+        app.world
+            .resource_mut::<RelationshipGraph>()
+            .relationships
+            .insert(("a".to_string(), "b".to_string()), relationship.clone());
+        app.world
+            .resource_mut::<UnfinishedConversations>()
+            .insert(ConversationResponse {
+                entity_a_uuid: "a".to_string(),
+                entity_b_uuid: "b".to_string(),
+                conversation: Vec::new(),
+                emotes: Vec::new(),
+            });
+        app.world
+            .resource_mut::<EntityPairConversationState>()
+            .pair_entities_in_conversation
+            .insert(
+                (entity_a, entity_b),
+                PairEntityConversationData {
+                    entity_a,
+                    entity_b,
+                    entity_a_pos: Vec3::new(16.0, 32.0, 1.0),
+                    entity_b_pos: Vec3::new(64.0, 32.0, 1.0),
+                    entity_a_id: "a".to_string(),
+                    entity_b_id: "b".to_string(),
+                    quadrant: Quadrants::TopLeft,
+                    chatting_icon_spawned: true,
+                    conversation_http_request_sent: true,
+                    path: [Vec2::new(48.0, 32.0)].into(),
+                },
+            );
+        app.world.resource_mut::<SimRng>().gen::<u64>();
+        app.world
+            .get_mut::<EntityRng>(entity_a)
+            .unwrap()
+            .gen::<u64>();
+        let next_sim_draw = app.world.resource::<SimRng>().clone().gen::<u64>();
+        let next_pet_draw = app
+            .world
+            .get::<EntityRng>(entity_a)
+            .unwrap()
+            .clone()
+            .gen::<u64>();
+        app.world.send_event(SaveGameEvent);
+        app.update();
+
+        // and everything that happened after
+        app.world
+            .get_mut::<Transform>(entity_a)
+            .unwrap()
+            .translation = Vec3::ZERO;
+        app.world
+            .get_mut::<RumorMemory>(entity_a)
+            .unwrap()
+            .rumors
+            .clear();
+        app.world.get_mut::<Mood>(entity_a).unwrap().dominant = BasicEmotions::Hate;
+        app.world.resource_mut::<GameClock>().day = 9;
+        app.world.resource_mut::<SimRng>().gen::<u64>();
+        app.world
+            .get_mut::<EntityRng>(entity_a)
+            .unwrap()
+            .gen::<u64>();
+        app.world
+            .resource_mut::<RumorLog>()
+            .start_rumor("b", "a", "a", BasicEmotions::Hate);
+        app.world
+            .resource_mut::<RelationshipGraph>()
+            .relationships
+            .clear();
+        app.world
+            .resource_mut::<UnfinishedConversations>()
+            .responses
+            .clear();
+        app.world
+            .resource_mut::<EntityPairConversationState>()
+            .pair_entities_in_conversation
+            .clear();
+
+        app.world.send_event(LoadGameEvent);
+        app.update();
+        std::fs::remove_file(&app.world.resource::<SaveSettings>().path).ok();
+
+        assert_eq!(pet_translation(&app, entity_a), Vec3::new(16.0, 32.0, 1.0));
+        assert_eq!(pet_translation(&app, entity_b), Vec3::new(64.0, 32.0, 1.0));
+        assert_eq!(
+            app.world.get::<Mood>(entity_a).unwrap().dominant,
+            BasicEmotions::Sadness
+        );
+        let rumor_memory = app.world.get::<RumorMemory>(entity_a).unwrap();
+        assert_eq!(rumor_memory.rumors.front(), Some(&rumor));
+        assert_eq!(rumor_memory.rumors.len(), 1);
+        assert_eq!(
+            app.world.get::<EntityActionState>(entity_a).unwrap().action,
+            GenericActions::Idle
+        );
+        assert_eq!(app.world.resource::<GameClock>().day, 3);
+        assert_eq!(
+            app.world.resource_mut::<SimRng>().gen::<u64>(),
+            next_sim_draw
+        );
+        assert_eq!(
+            app.world
+                .get_mut::<EntityRng>(entity_a)
+                .unwrap()
+                .gen::<u64>(),
+            next_pet_draw
+        );
+        assert_eq!(app.world.resource::<RumorLog>().next_rumor_id, 1);
+        assert_eq!(
+            app.world.resource::<RumorLog>().origin(rumor.id),
+            Some(&rumor)
+        );
+        assert_eq!(
+            app.world.resource::<RelationshipGraph>().get("a", "b"),
+            Some(&relationship)
+        );
+        assert!(app
+            .world
+            .resource_mut::<UnfinishedConversations>()
+            .take("b", "a")
+            .is_some());
+
+        let entity_pair_convo_state = app.world.resource::<EntityPairConversationState>();
+        let pair_convo_data =
+            &entity_pair_convo_state.pair_entities_in_conversation[&(entity_a, entity_b)];
+        assert_eq!(pair_convo_data.entity_b_id, "b");
+        assert_eq!(pair_convo_data.path, [Vec2::new(48.0, 32.0)]);
+        assert!(!pair_convo_data.chatting_icon_spawned);
+        assert!(entity_pair_convo_state.entities_in_conversation[&entity_b]);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::prelude::*;
+
+use crate::gameplay::player::components::BasicEmotions;
+
+#[derive(Debug, Clone, Default)]
+pub struct PetStats {
+    pub conversations: u32,
+    // game seconds spent with each emotion dominant
+    pub emotion_seconds: HashMap<BasicEmotions, f32>,
+}
+
+// What each pet got up to while the simulation ran, keyed by
+// `PlayerUniqueId` uuid
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SimulationStats {
+    pub elapsed_seconds: f32,
+    pub pets: HashMap<String, PetStats>,
+}
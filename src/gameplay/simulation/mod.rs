@@ -0,0 +1,100 @@
+use bevy::ecs::schedule::ExecutorKind;
+use bevy::input::InputPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+use super::components::FarmSet;
+use super::state::components::GameState;
+use super::state::{simulation_running, StatesPlugin};
+use super::FarmPlugin;
+use crate::http_request::HttpRequestPlugin;
+
+mod systems;
+use systems::{
+    hold_time_while_loading, record_conversations, record_emotions, release_time, start_simulation,
+};
+
+pub mod components;
+use components::SimulationStats;
+
+pub mod report;
+
+// Runs the farm without a window, renderer or audio, for long simulations on
+// CI boxes and in integration tests. Goes on top of `MinimalPlugins`:
+//
+//     App::new()
+//         .add_plugins((MinimalPlugins, SimulationPlugin::default()))
+//         .run();
+//
+// Add a custom `AssetPlugin` before it to load the farm from somewhere else.
+// Tests that drive the app with `app.update()` need `app.finish()` and
+// `app.cleanup()` first, as `run()` would, or images never finish loading.
+pub struct SimulationPlugin {
+    // how much game time each update advances, however fast the updates run
+    pub step: Duration,
+}
+
+impl Default for SimulationPlugin {
+    fn default() -> Self {
+        Self {
+            step: Duration::from_secs_f64(1.0 / 60.0),
+        }
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        // the parts of `DefaultPlugins` the farm needs that don't open a
+        // window. Images still load so sprite sheets and maps resolve, they
+        // just never reach a GPU. Input is registered but never arrives.
+        if !app.is_plugin_added::<TransformPlugin>() {
+            app.add_plugins(TransformPlugin);
+        }
+        if !app.is_plugin_added::<HierarchyPlugin>() {
+            app.add_plugins(HierarchyPlugin);
+        }
+        if !app.is_plugin_added::<InputPlugin>() {
+            app.add_plugins(InputPlugin);
+        }
+        if !app.is_plugin_added::<AssetPlugin>() {
+            app.add_plugins(AssetPlugin::default());
+        }
+        if !app.is_plugin_added::<ImagePlugin>() {
+            app.add_plugins(ImagePlugin::default_nearest());
+        }
+        // normally registered by `SpritePlugin`, which needs the renderer
+        app.init_asset::<TextureAtlasLayout>();
+
+        // no menus, the farm starts as soon as the pets are out
+        app.insert_state(GameState::Loading)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(self.step))
+            .add_plugins((StatesPlugin, HttpRequestPlugin, FarmPlugin))
+            .init_resource::<SimulationStats>()
+            .add_systems(OnEnter(GameState::Loading), hold_time_while_loading)
+            .add_systems(OnExit(GameState::Loading), release_time)
+            .add_systems(
+                Update,
+                start_simulation
+                    .run_if(in_state(GameState::Loading))
+                    .after(FarmSet::Load),
+            )
+            .add_systems(
+                FixedUpdate,
+                (record_conversations, record_emotions)
+                    .chain()
+                    .run_if(simulation_running)
+                    .after(FarmSet::Mood),
+            );
+    }
+
+    // the farm's systems are chained in `FarmSet` order anyway, and with a
+    // handful of pets handing them between threads costs more than it saves
+    fn finish(&self, app: &mut App) {
+        let mut schedules = app.world.resource_mut::<Schedules>();
+        for (_, schedule) in schedules.iter_mut() {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::components::{FarmTuning, InteractionTimers, Roaming};
+use crate::gameplay::gossip::components::RumorLog;
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::player::components::{BasicEmotions, EtherPet, PlayerData, PlayerUniqueId};
+use crate::gameplay::relationship::components::RelationshipGraph;
+use crate::gameplay::rng::components::SimRng;
+use crate::gameplay::simulation::components::SimulationStats;
+use crate::gameplay::tile::components::MapSettings;
+
+#[derive(Debug)]
+pub enum ReportError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Csv(csv::Error),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::Io(error) => write!(f, "could not write report: {}", error),
+            ReportError::Json(error) => write!(f, "could not encode report: {}", error),
+            ReportError::Csv(error) => write!(f, "could not write csv: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f32
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PetReport {
+    pub uuid: String,
+    pub name: String,
+    pub temperament: BasicEmotions,
+    pub dominant_emotion: BasicEmotions,
+    pub conversations: u32,
+    // game seconds spent with each emotion dominant
+    pub emotion_seconds: BTreeMap<&'static str, f32>,
+    // how this pet feels about everyone else on average, and they about it
+    pub mean_affinity_given: f32,
+    pub mean_affinity_received: f32,
+    pub friends: usize,
+    pub rivals: usize,
+    pub rumors_started: usize,
+    // other pets that heard the rumors it started, summed over the rumors
+    pub rumor_reach: usize,
+    // pets that heard any rumor about it
+    pub heard_about_by: usize,
+    // the tuning this pet ran with
+    pub chat_cooldown_seconds: f32,
+    pub roam_speed: f32,
+    pub roam_max_time: f32,
+    pub idle_max_time: f32,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RelationshipReport {
+    pub from_uuid: String,
+    pub to_uuid: String,
+    pub affinity: f32,
+    pub trust: f32,
+    pub familiarity: f32,
+    pub conversations: u32,
+}
+
+// Per-pet metrics at the end of a simulation run, for comparing tunings
+#[derive(Serialize, Debug, Clone)]
+pub struct SimulationReport {
+    // replays the run with GOSSIP_SEED or --seed
+    pub seed: u64,
+    pub days: u32,
+    pub simulated_seconds: f32,
+    pub map: String,
+    pub tuning: FarmTuning,
+    pub pets: Vec<PetReport>,
+    pub relationships: Vec<RelationshipReport>,
+}
+
+impl SimulationReport {
+    // days is the length the run was asked for, the world only knows elapsed seconds
+    pub fn collect(world: &mut World, days: u32) -> Self {
+        let mut pets_query = world.query_filtered::<(
+            &PlayerUniqueId,
+            &PlayerData,
+            &Mood,
+            &Roaming,
+            &InteractionTimers,
+        ), With<EtherPet>>();
+        let stats = world.resource::<SimulationStats>();
+        let relationship_graph = world.resource::<RelationshipGraph>();
+        let rumor_log = world.resource::<RumorLog>();
+
+        let uuids: Vec<String> = pets_query
+            .iter(world)
+            .map(|(unique_id, ..)| unique_id.uuid.clone())
+            .collect();
+
+        let mut pets: Vec<PetReport> = pets_query
+            .iter(world)
+            .map(
+                |(unique_id, player_data, mood, roaming, interaction_timers)| {
+                    let uuid = unique_id.uuid.as_str();
+                    let others: Vec<&str> = uuids
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|other| *other != uuid)
+                        .collect();
+                    let pet_stats = stats.pets.get(uuid).cloned().unwrap_or_default();
+
+                    let started: Vec<u64> = rumor_log
+                        .origins
+                        .values()
+                        .filter(|rumor| rumor.origin_uuid == uuid)
+                        .map(|rumor| rumor.id)
+                        .collect();
+                    let mut heard_about_by: Vec<String> = rumor_log
+                        .rumors_about(uuid)
+                        .iter()
+                        .flat_map(|rumor| rumor_log.carriers(rumor.id))
+                        .collect();
+                    heard_about_by.sort();
+                    heard_about_by.dedup();
+
+                    PetReport {
+                        uuid: uuid.to_string(),
+                        name: player_data.name.clone(),
+                        temperament: mood.temperament,
+                        dominant_emotion: mood.dominant,
+                        conversations: pet_stats.conversations,
+                        emotion_seconds: BasicEmotions::ALL
+                            .iter()
+                            .map(|emotion| {
+                                let seconds = pet_stats.emotion_seconds.get(emotion);
+                                (emotion.as_str(), seconds.copied().unwrap_or_default())
+                            })
+                            .collect(),
+                        mean_affinity_given: mean(
+                            others
+                                .iter()
+                                .map(|other| relationship_graph.affinity(uuid, other)),
+                        ),
+                        mean_affinity_received: mean(
+                            others
+                                .iter()
+                                .map(|other| relationship_graph.affinity(other, uuid)),
+                        ),
+                        friends: others
+                            .iter()
+                            .filter(|other| relationship_graph.are_friends(uuid, other))
+                            .count(),
+                        rivals: others
+                            .iter()
+                            .filter(|other| relationship_graph.are_rivals(uuid, other))
+                            .count(),
+                        rumors_started: started.len(),
+                        rumor_reach: started
+                            .iter()
+                            .map(|rumor_id| rumor_log.carriers(*rumor_id).len().saturating_sub(1))
+                            .sum(),
+                        heard_about_by: heard_about_by.len(),
+                        chat_cooldown_seconds: interaction_timers
+                            .prox_chat_cooldown_timer
+                            .duration()
+                            .as_secs_f32(),
+                        roam_speed: roaming.roam_speed,
+                        roam_max_time: roaming.roam_max_time,
+                        idle_max_time: roaming.idle_max_time,
+                    }
+                },
+            )
+            .collect();
+        pets.sort_by(|a, b| a.uuid.cmp(&b.uuid));
+
+        let mut relationships: Vec<RelationshipReport> = relationship_graph
+            .relationships
+            .iter()
+            .map(|((from_uuid, to_uuid), relationship)| RelationshipReport {
+                from_uuid: from_uuid.clone(),
+                to_uuid: to_uuid.clone(),
+                affinity: relationship.affinity,
+                trust: relationship.trust,
+                familiarity: relationship.familiarity,
+                conversations: relationship.conversations,
+            })
+            .collect();
+        relationships.sort_by(|a, b| (&a.from_uuid, &a.to_uuid).cmp(&(&b.from_uuid, &b.to_uuid)));
+
+        Self {
+            seed: world.resource::<SimRng>().seed(),
+            days,
+            simulated_seconds: stats.elapsed_seconds,
+            map: world.resource::<MapSettings>().path.clone(),
+            tuning: world.resource::<FarmTuning>().clone(),
+            pets,
+            relationships,
+        }
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<(), ReportError> {
+        let json = serde_json::to_string_pretty(self).map_err(ReportError::Json)?;
+        std::fs::write(path, json).map_err(ReportError::Io)
+    }
+
+    // one row per pet, emotions spread over a column each
+    pub fn write_pets_csv(&self, path: &Path) -> Result<(), ReportError> {
+        let mut writer = csv::Writer::from_path(path).map_err(ReportError::Csv)?;
+
+        let mut header: Vec<String> = [
+            "uuid",
+            "name",
+            "temperament",
+            "dominant_emotion",
+            "conversations",
+        ]
+        .iter()
+        .map(|column| column.to_string())
+        .collect();
+        header.extend(
+            BasicEmotions::ALL
+                .iter()
+                .map(|emotion| format!("{}_seconds", emotion.as_str().to_lowercase())),
+        );
+        header.extend(
+            [
+                "mean_affinity_given",
+                "mean_affinity_received",
+                "friends",
+                "rivals",
+                "rumors_started",
+                "rumor_reach",
+                "heard_about_by",
+                "chat_cooldown_seconds",
+                "roam_speed",
+                "roam_max_time",
+                "idle_max_time",
+            ]
+            .iter()
+            .map(|column| column.to_string()),
+        );
+        writer.write_record(&header).map_err(ReportError::Csv)?;
+
+        for pet in self.pets.iter() {
+            let mut record = vec![
+                pet.uuid.clone(),
+                pet.name.clone(),
+                pet.temperament.as_str().to_string(),
+                pet.dominant_emotion.as_str().to_string(),
+                pet.conversations.to_string(),
+            ];
+            record.extend(BasicEmotions::ALL.iter().map(|emotion| {
+                pet.emotion_seconds
+                    .get(emotion.as_str())
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string()
+            }));
+            record.extend([
+                pet.mean_affinity_given.to_string(),
+                pet.mean_affinity_received.to_string(),
+                pet.friends.to_string(),
+                pet.rivals.to_string(),
+                pet.rumors_started.to_string(),
+                pet.rumor_reach.to_string(),
+                pet.heard_about_by.to_string(),
+                pet.chat_cooldown_seconds.to_string(),
+                pet.roam_speed.to_string(),
+                pet.roam_max_time.to_string(),
+                pet.idle_max_time.to_string(),
+            ]);
+            writer.write_record(&record).map_err(ReportError::Csv)?;
+        }
+        writer.flush().map_err(ReportError::Io)
+    }
+
+    pub fn write_relationships_csv(&self, path: &Path) -> Result<(), ReportError> {
+        let mut writer = csv::Writer::from_path(path).map_err(ReportError::Csv)?;
+        for relationship in self.relationships.iter() {
+            writer.serialize(relationship).map_err(ReportError::Csv)?;
+        }
+        writer.flush().map_err(ReportError::Io)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::mood::components::Mood;
+use crate::gameplay::player::components::{EtherPet, PlayerUniqueId};
+use crate::gameplay::simulation::components::SimulationStats;
+use crate::gameplay::state::components::GameState;
+
+// events
+use crate::http_request::events::ConversationHistoryEvent;
+
+// Loading takes however many updates the assets need, which differs between
+// runs. Game time stands still until the farm is ready so cows and timers
+// don't get a head start that changes the rest of the run.
+pub fn hold_time_while_loading(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.pause();
+}
+
+pub fn release_time(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.unpause();
+}
+
+// the clock only starts once the map and pets are in, however long the
+// assets take to load
+pub fn start_simulation(
+    pets_query: Query<(), With<EtherPet>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if !pets_query.is_empty() {
+        next_game_state.set(GameState::Playing);
+    }
+}
+
+pub fn record_conversations(
+    mut conversation_history_event_reader: EventReader<ConversationHistoryEvent>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    for event in conversation_history_event_reader.read() {
+        for uuid in [&event.response.entity_a_uuid, &event.response.entity_b_uuid] {
+            stats.pets.entry(uuid.clone()).or_default().conversations += 1;
+        }
+    }
+}
+
+pub fn record_emotions(
+    time: Res<Time>,
+    mut stats: ResMut<SimulationStats>,
+    mood_query: Query<(&PlayerUniqueId, &Mood), With<EtherPet>>,
+) {
+    let delta_seconds = time.delta_seconds();
+    stats.elapsed_seconds += delta_seconds;
+    for (unique_id, mood) in mood_query.iter() {
+        *stats
+            .pets
+            .entry(unique_id.uuid.clone())
+            .or_default()
+            .emotion_seconds
+            .entry(mood.dominant)
+            .or_insert(0.0) += delta_seconds;
+    }
+}
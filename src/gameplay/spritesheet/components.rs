@@ -1,91 +1,55 @@
-use crate::prelude::*;
-
-#[derive(Resource, Debug)]
-pub struct PlayerSpriteSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct PurpleCowSpriteSheet(pub Handle<TextureAtlas>);
-
-// Spritesheets by Layer
-#[derive(Resource, Debug)]
-pub struct ForestDetailsV2Sheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct RampsSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct AppleTreeSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct RegularTreeSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct WoodenHouseSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct WaterObjectsSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct BushesSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct FencesSheet(pub Handle<TextureAtlas>);
+use bevy::reflect::TypePath;
+use serde::Deserialize;
 
-#[derive(Resource, Debug)]
-pub struct ForestDetailsSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct DarkerGrassPatchSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct DarkGrassPatchSheet(pub Handle<TextureAtlas>);
-
-#[derive(Resource, Debug)]
-pub struct SoilSpriteSheet(pub Handle<TextureAtlas>);
+use crate::prelude::*;
 
-#[derive(Resource, Debug)]
-pub struct DarkGrassHillsSpriteSheet(pub Handle<TextureAtlas>);
+pub const SPRITESHEET_MANIFEST_PATH: &str = "spritesheets/manifest.json";
 
-#[derive(Resource, Debug)]
-pub struct DarkGrassWaterSpriteSheet(pub Handle<TextureAtlas>);
+// ids used by the code, every other sheet in the manifest is looked up by
+// whatever id it was given there
+pub const PLAYER_SHEET: &str = "player";
+pub const PURPLE_COW_SHEET: &str = "purple_cow";
+pub const CHATTING_ICON_SHEET: &str = "chatting_icon";
+pub const EMOTION_ICON_SHEET: &str = "emotion_icon";
 
-#[derive(Resource, Debug)]
-pub struct WaterSpriteSheet(pub Handle<TextureAtlas>);
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpriteSheetInfo {
+    pub id: String,
+    // relative to `assets/`
+    pub path: String,
+    pub sprite_size: [f32; 2],
+    pub columns: usize,
+    pub rows: usize,
+    #[serde(default)]
+    pub padding: Option<[f32; 2]>,
+    #[serde(default)]
+    pub offset: Option<[f32; 2]>,
+}
 
-#[derive(Resource, Debug)]
-pub struct ChattingIconSheet(pub Handle<TextureAtlas>);
+#[derive(Asset, TypePath, Debug)]
+pub struct SpriteSheetManifest {
+    pub sheets: Vec<SpriteSheetInfo>,
+    // one image per sheet, same order as `sheets`
+    pub textures: Vec<Handle<Image>>,
+}
 
 #[derive(Resource, Debug)]
-pub struct EmotionIconSheet(pub Handle<TextureAtlas>);
+pub struct SpriteSheetManifestHandle(pub Handle<SpriteSheetManifest>);
 
-pub enum SpriteSheetResource {
-    Player(PlayerSpriteSheet),
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
 
-    // Spritesheets by Layer
-    ForestDetailsV2(ForestDetailsV2Sheet),   // 15th Layer
-    Ramps(RampsSheet),                       // 14th Layer
-    AppleTree(AppleTreeSheet),               // 13th Layer
-    RegularTree(RegularTreeSheet),           // 12th Layer
-    WoodenHouse(WoodenHouseSheet),           // 11th Layer
-    WaterObjects(WaterObjectsSheet),         // 10th Layer
-    Bushes(BushesSheet),                     // 9th Layer
-    Fences(FencesSheet),                     // 8th Layer
-    ForestDetails(ForestDetailsSheet),       // 7th Layer
-    DarkerGrassPatch(DarkerGrassPatchSheet), // 6th Layer
-    GrassPatch(DarkGrassPatchSheet),         // 5th layer
-    Soil(SoilSpriteSheet),                   // 4rd Layer
-    SecondGround(DarkGrassHillsSpriteSheet), // 3rd Layer
-    Ground(DarkGrassWaterSpriteSheet),       // 2nd Layer
-    Water(WaterSpriteSheet),                 // 1st Layer
-    PurpleCow(PurpleCowSpriteSheet),
-    ChattingIcon(ChattingIconSheet),
-    EmotionIcon(EmotionIconSheet),
+#[derive(Resource, Default, Debug)]
+pub struct SpriteSheets {
+    pub sheets: HashMap<String, SpriteSheet>,
+    pub loaded: bool,
 }
 
-pub struct SpriteSheetInfo {
-    pub url: &'static str,
-    pub sprite_size: Vec2,
-    pub columns: usize,
-    pub rows: usize,
-    pub resource_type: fn(Handle<TextureAtlas>) -> SpriteSheetResource,
+impl SpriteSheets {
+    pub fn get(&self, id: &str) -> Option<&SpriteSheet> {
+        self.sheets.get(id)
+    }
 }
@@ -0,0 +1,83 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+use std::fmt;
+
+use super::components::{SpriteSheetInfo, SpriteSheetManifest};
+
+#[derive(Deserialize, Debug)]
+struct SpriteSheetManifestJson {
+    sheets: Vec<SpriteSheetInfo>,
+}
+
+#[derive(Debug)]
+pub enum SpriteSheetManifestError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    DuplicateId(String),
+}
+
+impl fmt::Display for SpriteSheetManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetManifestError::Io(error) => {
+                write!(f, "could not read spritesheet manifest: {}", error)
+            }
+            SpriteSheetManifestError::Json(error) => {
+                write!(f, "could not parse spritesheet manifest: {}", error)
+            }
+            SpriteSheetManifestError::DuplicateId(id) => {
+                write!(f, "spritesheet id {:?} is used more than once", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpriteSheetManifestError {}
+
+#[derive(Default)]
+pub struct SpriteSheetManifestLoader;
+
+impl AssetLoader for SpriteSheetManifestLoader {
+    type Asset = SpriteSheetManifest;
+    type Settings = ();
+    type Error = SpriteSheetManifestError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<SpriteSheetManifest, SpriteSheetManifestError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(SpriteSheetManifestError::Io)?;
+            let manifest: SpriteSheetManifestJson =
+                serde_json::from_slice(&bytes).map_err(SpriteSheetManifestError::Json)?;
+
+            let mut textures = Vec::new();
+            for (index, info) in manifest.sheets.iter().enumerate() {
+                if manifest.sheets[..index]
+                    .iter()
+                    .any(|other| other.id == info.id)
+                {
+                    return Err(SpriteSheetManifestError::DuplicateId(info.id.clone()));
+                }
+                textures.push(load_context.load(info.path.clone()));
+            }
+
+            Ok(SpriteSheetManifest {
+                sheets: manifest.sheets,
+                textures,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
@@ -1,14 +1,24 @@
 use crate::prelude::*;
 
+use super::components::FarmSet;
+
 pub mod systems;
-use systems::load_spritesheets;
+use systems::{load_spritesheet_manifest, register_spritesheets};
 
 pub mod components;
+use components::{SpriteSheetManifest, SpriteSheets};
+
+pub mod manifest;
+use manifest::SpriteSheetManifestLoader;
 
 pub struct SpriteSheetPlugin;
 
 impl Plugin for SpriteSheetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(load_spritesheets.in_base_set(StartupSet::PreStartup));
+        app.init_asset::<SpriteSheetManifest>()
+            .init_asset_loader::<SpriteSheetManifestLoader>()
+            .init_resource::<SpriteSheets>()
+            .add_systems(PreStartup, load_spritesheet_manifest)
+            .add_systems(Update, register_spritesheets.in_set(FarmSet::Load));
     }
 }
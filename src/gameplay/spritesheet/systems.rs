@@ -1,361 +1,153 @@
 use crate::gameplay::spritesheet::components::{
-    AppleTreeSheet, BushesSheet, ChattingIconSheet, DarkGrassHillsSpriteSheet, DarkGrassPatchSheet,
-    DarkGrassWaterSpriteSheet, DarkerGrassPatchSheet, EmotionIconSheet, FencesSheet,
-    ForestDetailsSheet, ForestDetailsV2Sheet, PlayerSpriteSheet, PurpleCowSpriteSheet, RampsSheet,
-    RegularTreeSheet, SoilSpriteSheet, SpriteSheetInfo, SpriteSheetResource, WaterObjectsSheet,
-    WaterSpriteSheet, WoodenHouseSheet,
+    SpriteSheet, SpriteSheetManifest, SpriteSheetManifestHandle, SpriteSheets,
+    SPRITESHEET_MANIFEST_PATH,
 };
-use crate::gameplay::tile::components::WaterSpriteAnimation;
 use crate::prelude::*;
 
-const SPRITE_SHEETS: &[SpriteSheetInfo] = &[
-    SpriteSheetInfo {
-        url: "spritesheets/spritesheet.png",
-        sprite_size: Vec2::new(48.0, 48.0),
-        columns: 8,
-        rows: 24,
-        resource_type: |handle| SpriteSheetResource::Player(PlayerSpriteSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/purple_cow.png",
-        sprite_size: Vec2::new(32.0, 32.0),
-        columns: 8,
-        rows: 9,
-        resource_type: |handle| SpriteSheetResource::PurpleCow(PurpleCowSpriteSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/forest_details_v2.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 12,
-        rows: 7,
-        resource_type: |handle| SpriteSheetResource::ForestDetailsV2(ForestDetailsV2Sheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/dark_hills_tall.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 6,
-        rows: 8,
-        resource_type: |handle| SpriteSheetResource::Ramps(RampsSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/apple_tree.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 36,
-        rows: 15,
-        resource_type: |handle| SpriteSheetResource::AppleTree(AppleTreeSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/regular_tree.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 36,
-        rows: 15,
-        resource_type: |handle| SpriteSheetResource::RegularTree(RegularTreeSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/wooden_house.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 6,
-        rows: 5,
-        resource_type: |handle| SpriteSheetResource::WoodenHouse(WoodenHouseSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/water_objects.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 12,
-        rows: 2,
-        resource_type: |handle| SpriteSheetResource::WaterObjects(WaterObjectsSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/bushes.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 11,
-        rows: 12,
-        resource_type: |handle| SpriteSheetResource::Bushes(BushesSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/fences.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 8,
-        rows: 4,
-        resource_type: |handle| SpriteSheetResource::Fences(FencesSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/forest_details.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 12,
-        rows: 5,
-        resource_type: |handle| SpriteSheetResource::ForestDetails(ForestDetailsSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/darker_grass_patch.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 11,
-        rows: 7,
-        resource_type: |handle| {
-            SpriteSheetResource::DarkerGrassPatch(DarkerGrassPatchSheet(handle))
-        },
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/dark_grass_patch.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 11,
-        rows: 7,
-        resource_type: |handle| SpriteSheetResource::GrassPatch(DarkGrassPatchSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/soil.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 11,
-        rows: 7,
-        resource_type: |handle| SpriteSheetResource::Soil(SoilSpriteSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/dark_grass_hills.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 11,
-        rows: 7,
-        resource_type: |handle| {
-            SpriteSheetResource::SecondGround(DarkGrassHillsSpriteSheet(handle))
-        },
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/dark_grass_water.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 9,
-        rows: 8,
-        resource_type: |handle| SpriteSheetResource::Ground(DarkGrassWaterSpriteSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "spritesheets/water.png",
-        sprite_size: Vec2::new(16.0, 16.0),
-        columns: 4,
-        rows: 1,
-        resource_type: |handle| SpriteSheetResource::Water(WaterSpriteSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "icons/chatting_icon.png",
-        sprite_size: Vec2::new(48.0, 48.0),
-        columns: 4,
-        rows: 3,
-        resource_type: |handle| SpriteSheetResource::ChattingIcon(ChattingIconSheet(handle)),
-    },
-    SpriteSheetInfo {
-        url: "icons/emotion_icons.png",
-        sprite_size: Vec2::new(24.0, 24.0),
-        columns: 6,
-        rows: 1,
-        resource_type: |handle| SpriteSheetResource::EmotionIcon(EmotionIconSheet(handle)),
-    },
-];
+pub fn load_spritesheet_manifest(mut commands: Commands, assets: Res<AssetServer>) {
+    let handle = assets.load(SPRITESHEET_MANIFEST_PATH);
+    commands.insert_resource(SpriteSheetManifestHandle(handle));
+}
 
-pub fn load_spritesheets(
-    mut commands: Commands,
-    assets: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+// (Re)builds the registry whenever the manifest finishes loading, so edits
+// to the manifest are picked up with asset hot reloading
+pub fn register_spritesheets(
+    mut manifest_events: EventReader<AssetEvent<SpriteSheetManifest>>,
+    manifest_handle: Res<SpriteSheetManifestHandle>,
+    manifests: Res<Assets<SpriteSheetManifest>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut sprite_sheets: ResMut<SpriteSheets>,
 ) {
-    for info in SPRITE_SHEETS {
-        let texture_handle = assets.load(info.url);
-        let texture_atlas = TextureAtlas::from_grid(
-            texture_handle,
-            info.sprite_size,
-            info.columns,
-            info.rows,
-            None,
-            None,
-        );
-        let texture_atlas_handle = texture_atlases.add(texture_atlas);
-        let resource = (info.resource_type)(texture_atlas_handle);
-        match resource {
-            SpriteSheetResource::Player(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::PurpleCow(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::ForestDetailsV2(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Ramps(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::AppleTree(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::RegularTree(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::WoodenHouse(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::WaterObjects(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Bushes(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Fences(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::ForestDetails(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::DarkerGrassPatch(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::GrassPatch(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Soil(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::SecondGround(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Ground(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::Water(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::ChattingIcon(res) => {
-                commands.insert_resource(res);
-            }
-            SpriteSheetResource::EmotionIcon(res) => {
-                commands.insert_resource(res);
-            }
+    for event in manifest_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != manifest_handle.0.id() {
+            continue;
         }
+        let Some(manifest) = manifests.get(*id) else {
+            continue;
+        };
+
+        sprite_sheets.sheets.clear();
+        for (info, texture) in manifest.sheets.iter().zip(manifest.textures.iter()) {
+            let layout = TextureAtlasLayout::from_grid(
+                Vec2::from(info.sprite_size),
+                info.columns,
+                info.rows,
+                info.padding.map(Vec2::from),
+                info.offset.map(Vec2::from),
+            );
+            sprite_sheets.sheets.insert(
+                info.id.clone(),
+                SpriteSheet {
+                    texture: texture.clone(),
+                    layout: texture_atlas_layouts.add(layout),
+                },
+            );
+        }
+        sprite_sheets.loaded = true;
     }
 }
 
-pub fn spawn_player_spritesheet_sprite(
+pub fn spritesheets_loaded(sprite_sheets: Res<SpriteSheets>) -> bool {
+    sprite_sheets.loaded
+}
+
+pub fn spawn_spritesheet_sprite(
     commands: &mut Commands,
-    player_spritesheet: &PlayerSpriteSheet,
+    spritesheet: &SpriteSheet,
     index: usize,
-    translation: Vec3,
+    transform: Transform,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
     commands
         .spawn(SpriteSheetBundle {
-            atlas: player_spritesheet.0.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                ..Default::default()
+            texture: spritesheet.texture.clone(),
+            atlas: TextureAtlas {
+                layout: spritesheet.layout.clone(),
+                index,
             },
+            transform,
             ..Default::default()
         })
         .id()
 }
 
-pub fn spawn_cow_spritesheet_sprite(
+pub fn spawn_player_spritesheet_sprite(
     commands: &mut Commands,
-    purple_cow_spritesheet: &PurpleCowSpriteSheet,
+    player_spritesheet: &SpriteSheet,
     index: usize,
     translation: Vec3,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
-    commands
-        .spawn(SpriteSheetBundle {
-            atlas: purple_cow_spritesheet.0.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .id()
+    spawn_spritesheet_sprite(
+        commands,
+        player_spritesheet,
+        index,
+        Transform::from_translation(translation),
+    )
 }
 
-pub fn spawn_map(
+pub fn spawn_cow_spritesheet_sprite(
     commands: &mut Commands,
-    spritesheet: &TextureAtlas,
+    purple_cow_spritesheet: &SpriteSheet,
     index: usize,
     translation: Vec3,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
-    commands
-        .spawn(SpriteSheetBundle {
-            atlas: spritesheet.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .id()
+    spawn_spritesheet_sprite(
+        commands,
+        purple_cow_spritesheet,
+        index,
+        Transform::from_translation(translation),
+    )
 }
 
-pub fn spawn_water_spritesheet_sprite(
+pub fn spawn_map(
     commands: &mut Commands,
-    water_spritesheet: &WaterSpriteSheet,
+    texture: &Handle<Image>,
+    layout: &Handle<TextureAtlasLayout>,
     index: usize,
     translation: Vec3,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
-    commands
-        .spawn(SpriteSheetBundle {
-            atlas: water_spritesheet.0.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .insert(WaterSpriteAnimation {
-            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
-            frame_count: 4,
-            current_frame: 0,
-            base_index: 0,
-        })
-        .id()
+    spawn_spritesheet_sprite(
+        commands,
+        &SpriteSheet {
+            texture: texture.clone(),
+            layout: layout.clone(),
+        },
+        index,
+        Transform::from_translation(translation),
+    )
 }
 
 pub fn spawn_chatting_sprite(
     commands: &mut Commands,
-    chatting_icon: &ChattingIconSheet,
+    chatting_icon: &SpriteSheet,
     index: usize,
     translation: Vec3,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
-    commands
-        .spawn(SpriteSheetBundle {
-            atlas: chatting_icon.0.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                scale: Vec3::new(0.65, 0.65, 0.65),
-                ..Default::default()
-            },
+    spawn_spritesheet_sprite(
+        commands,
+        chatting_icon,
+        index,
+        Transform {
+            translation,
+            scale: Vec3::new(0.65, 0.65, 0.65),
             ..Default::default()
-        })
-        .id()
+        },
+    )
 }
 
 pub fn spawn_emote_sprite(
     commands: &mut Commands,
-    emotion_icon: &EmotionIconSheet,
+    emotion_icon: &SpriteSheet,
     index: usize,
     translation: Vec3,
 ) -> Entity {
-    let sprite = TextureAtlas::new(index);
-
-    commands
-        .spawn(SpriteSheetBundle {
-            atlas: emotion_icon.0.clone(),
-            sprite,
-            transform: Transform {
-                translation,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .id()
+    spawn_spritesheet_sprite(
+        commands,
+        emotion_icon,
+        index,
+        Transform::from_translation(translation),
+    )
 }
@@ -27,3 +27,31 @@ pub enum HttpRequestState {
     Success,
     Error,
 }
+
+// How fast game time runs against the wall clock while Playing. Pausing is
+// GameState::Pause, which stops virtual time whatever the speed.
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SimulationSpeed {
+    #[default]
+    Normal,
+    Double,
+    FastForward,
+}
+
+impl SimulationSpeed {
+    pub fn relative_speed(&self) -> f64 {
+        match self {
+            SimulationSpeed::Normal => 1.0,
+            SimulationSpeed::Double => 2.0,
+            SimulationSpeed::FastForward => 8.0,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SimulationSpeed::Normal => "1x",
+            SimulationSpeed::Double => "2x",
+            SimulationSpeed::FastForward => "8x",
+        }
+    }
+}
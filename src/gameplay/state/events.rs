@@ -0,0 +1,5 @@
+use crate::prelude::*;
+
+// runs the fixed schedule once while paused
+#[derive(Event, Debug)]
+pub struct StepSimulationEvent;
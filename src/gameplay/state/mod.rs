@@ -1,17 +1,42 @@
 use crate::prelude::*;
 
 mod systems;
-use systems::{transition_to_game_state, transition_to_main_menu_state};
+pub use systems::simulation_running;
+use systems::{
+    apply_simulation_speed, pause_virtual_time, resume_virtual_time, simulation_speed_hotkeys,
+    step_simulation, step_simulation_hotkey, toggle_pause_state, transition_to_loading_state,
+    transition_to_main_menu_state,
+};
 
 pub mod components;
-use components::{CameraState, GameState, HttpRequestState};
+use components::{CameraState, GameState, HttpRequestState, SimulationSpeed};
+
+pub mod events;
+use events::StepSimulationEvent;
+
 pub struct StatesPlugin;
 
 impl Plugin for StatesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_state::<GameState>()
-            .add_state::<CameraState>()
-            .add_state::<HttpRequestState>()
-            .add_systems((transition_to_game_state, transition_to_main_menu_state));
+        app.init_state::<GameState>()
+            .init_state::<CameraState>()
+            .init_state::<HttpRequestState>()
+            .init_resource::<SimulationSpeed>()
+            .add_event::<StepSimulationEvent>()
+            .add_systems(OnEnter(GameState::Pause), pause_virtual_time)
+            .add_systems(OnExit(GameState::Pause), resume_virtual_time)
+            .add_systems(
+                Update,
+                (
+                    transition_to_loading_state,
+                    toggle_pause_state,
+                    transition_to_main_menu_state,
+                    simulation_speed_hotkeys.run_if(simulation_running),
+                    apply_simulation_speed.run_if(resource_changed::<SimulationSpeed>),
+                    (step_simulation_hotkey, step_simulation)
+                        .chain()
+                        .run_if(in_state(GameState::Pause)),
+                ),
+            );
     }
 }
@@ -1,38 +1,111 @@
-use super::components::{CameraState, GameState};
+use bevy::app::FixedMain;
+
+use super::components::{CameraState, GameState, SimulationSpeed};
+use super::events::StepSimulationEvent;
 use crate::prelude::*;
 
-pub fn _pause_game(mut game_state_next_state: ResMut<NextState<GameState>>) {
-    game_state_next_state.set(GameState::Pause);
+// the simulation stands still while the pause menu is open
+pub fn pause_virtual_time(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.pause();
 }
 
-pub fn _resume_game(mut game_state_next_state: ResMut<NextState<GameState>>) {
-    game_state_next_state.set(GameState::Playing);
+pub fn resume_virtual_time(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.unpause();
 }
 
-pub fn transition_to_main_menu_state(
+// the fixed tick, and every timer ticked on it, follows the virtual clock
+pub fn apply_simulation_speed(
+    simulation_speed: Res<SimulationSpeed>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    virtual_time.set_relative_speed_f64(simulation_speed.relative_speed());
+}
+
+pub fn simulation_speed_hotkeys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    app_state: Res<State<GameState>>,
+    mut app_state_next_state: ResMut<NextState<GameState>>,
+    mut simulation_speed: ResMut<SimulationSpeed>,
+) {
+    let speed = if keyboard_input.just_pressed(KeyCode::Digit1) {
+        SimulationSpeed::Normal
+    } else if keyboard_input.just_pressed(KeyCode::Digit2) {
+        SimulationSpeed::Double
+    } else if keyboard_input.just_pressed(KeyCode::Digit3) {
+        SimulationSpeed::FastForward
+    } else {
+        return;
+    };
+    *simulation_speed = speed;
+    // picking a speed while paused plays at it
+    if *app_state.get() == GameState::Pause {
+        app_state_next_state.set(GameState::Playing);
+    }
+}
+
+pub fn step_simulation_hotkey(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut step_simulation_event_writer: EventWriter<StepSimulationEvent>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Period) {
+        step_simulation_event_writer.send(StepSimulationEvent);
+    }
+}
+
+// Virtual time is paused, so the fixed loop would never run. Advance the fixed
+// clock by hand and run it once per step, the same way RunFixedMainLoop does.
+pub fn step_simulation(world: &mut World) {
+    let steps = world
+        .resource_mut::<Events<StepSimulationEvent>>()
+        .drain()
+        .count();
+    for _ in 0..steps {
+        let timestep = world.resource::<Time<Fixed>>().timestep();
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        world.run_schedule(FixedMain);
+    }
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+// Playing, or paused and only moving when stepped
+pub fn simulation_running(app_state: Res<State<GameState>>) -> bool {
+    matches!(app_state.get(), GameState::Playing | GameState::Pause)
+}
+
+pub fn toggle_pause_state(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     app_state: Res<State<GameState>>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
-    mut camera_state_next_state: ResMut<NextState<CameraState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::P) {
-        if app_state.current() != GameState::MainMenu {
-            camera_state_next_state.set(CameraState::MainMenuCamera);
-            app_state_next_state.set(GameState::MainMenu);
+    if keyboard_input.just_pressed(KeyCode::KeyP) {
+        match app_state.get() {
+            GameState::Playing => app_state_next_state.set(GameState::Pause),
+            GameState::Pause => app_state_next_state.set(GameState::Playing),
+            _ => {}
         }
     }
 }
 
-pub fn transition_to_game_state(
+pub fn transition_to_main_menu_state(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     app_state: Res<State<GameState>>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
     mut camera_state_next_state: ResMut<NextState<CameraState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Return) {
-        if app_state.current() != GameState::Playing {
-            camera_state_next_state.set(CameraState::ManualCameraControl);
-            app_state_next_state.set(GameState::Playing);
-        }
+    if keyboard_input.just_pressed(KeyCode::KeyM) && *app_state.get() == GameState::Pause {
+        camera_state_next_state.set(CameraState::MainMenuCamera);
+        app_state_next_state.set(GameState::MainMenu);
+    }
+}
+
+// the loading screen moves on to Playing once the assets are in
+pub fn transition_to_loading_state(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    app_state: Res<State<GameState>>,
+    mut app_state_next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Enter) && *app_state.get() == GameState::MainMenu {
+        app_state_next_state.set(GameState::Loading);
     }
 }
@@ -0,0 +1,215 @@
+use crate::prelude::*;
+
+use crate::gameplay::components::BasicEntityCollider;
+
+// Solid tiles of the loaded map, one flag per cell. Built once when the map
+// spawns so collision checks are lookups instead of a scan over every
+// collider entity. Cells outside the map count as solid.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CollisionGrid {
+    pub width: usize,
+    pub height: usize,
+    pub tile_size: Vec2,
+    pub solid: Vec<bool>,
+}
+
+impl CollisionGrid {
+    pub fn new(width: usize, height: usize, tile_size: Vec2) -> Self {
+        Self {
+            width,
+            height,
+            tile_size,
+            solid: vec![false; width * height],
+        }
+    }
+
+    fn half_map_size(&self) -> Vec2 {
+        Vec2::new(
+            self.width as f32 * self.tile_size.x,
+            self.height as f32 * self.tile_size.y,
+        ) / 2.0
+    }
+
+    pub fn in_bounds(&self, tile: IVec2) -> bool {
+        tile.x >= 0
+            && tile.y >= 0
+            && (tile.x as usize) < self.width
+            && (tile.y as usize) < self.height
+    }
+
+    pub fn set_solid(&mut self, x: usize, y: usize, solid: bool) {
+        if x < self.width && y < self.height {
+            self.solid[y * self.width + x] = solid;
+        }
+    }
+
+    pub fn is_solid(&self, tile: IVec2) -> bool {
+        !self.in_bounds(tile) || self.solid[tile.y as usize * self.width + tile.x as usize]
+    }
+
+    // Tile centers sit at the same positions the map tiles are spawned at
+    pub fn tile_to_world(&self, tile: IVec2) -> Vec2 {
+        let half_map_size = self.half_map_size();
+        Vec2::new(
+            tile.x as f32 * self.tile_size.x - half_map_size.x,
+            -(tile.y as f32) * self.tile_size.y + half_map_size.y,
+        )
+    }
+
+    pub fn world_to_tile(&self, position: Vec2) -> IVec2 {
+        let half_map_size = self.half_map_size();
+        IVec2::new(
+            ((position.x + half_map_size.x + self.tile_size.x / 2.0) / self.tile_size.x).floor()
+                as i32,
+            ((half_map_size.y + self.tile_size.y / 2.0 - position.y) / self.tile_size.y).floor()
+                as i32,
+        )
+    }
+
+    pub fn is_blocked(&self, position: Vec2) -> bool {
+        self.is_solid(self.world_to_tile(position))
+    }
+
+    // true when a box centered on `center` overlaps any solid tile
+    pub fn aabb_collides(&self, center: Vec2, half_size: Vec2) -> bool {
+        // shrink the far edges a hair so a box resting on a tile edge
+        // doesn't count the neighbouring tile
+        let min = self.world_to_tile(center - half_size);
+        let max = self.world_to_tile(center + half_size - Vec2::splat(0.001));
+
+        // world y points up while tile rows go down
+        for y in max.y..=min.y {
+            for x in min.x..=max.x {
+                if self.is_solid(IVec2::new(x, y)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Moves a box by `delta` one axis at a time, stopping each axis at the
+    // last free position. Returns the new center, so entities slide along
+    // walls instead of sticking to them.
+    pub fn sweep_aabb(&self, center: Vec2, half_size: Vec2, delta: Vec2) -> Vec2 {
+        let mut position = center;
+        position.x += self
+            .sweep_axis(position, half_size, Vec2::new(delta.x, 0.0))
+            .x;
+        position.y += self
+            .sweep_axis(position, half_size, Vec2::new(0.0, delta.y))
+            .y;
+        position
+    }
+
+    fn sweep_axis(&self, center: Vec2, half_size: Vec2, delta: Vec2) -> Vec2 {
+        let distance = delta.length();
+        if distance == 0.0 {
+            return Vec2::ZERO;
+        }
+
+        // step at most a quarter tile so thin walls can't be skipped
+        let max_step = self.tile_size.min_element() / 4.0;
+        let steps = (distance / max_step).ceil().max(1.0) as usize;
+        let step = delta / steps as f32;
+
+        let mut moved = Vec2::ZERO;
+        for _ in 0..steps {
+            if self.aabb_collides(center + moved + step, half_size) {
+                break;
+            }
+            moved += step;
+        }
+        moved
+    }
+}
+
+// Only the pet's feet collide with the map, so it can walk right up to
+// trees and houses that are drawn over it
+pub fn entity_footprint(collider: &BasicEntityCollider) -> Vec2 {
+    Vec2::new(collider.width, collider.height) / 4.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE: f32 = 16.0;
+    const HALF_SIZE: Vec2 = Vec2::splat(4.0);
+
+    // 4x4 map with the third column solid, tile (0, 0) sits at (-32, 32)
+    fn walled_grid() -> CollisionGrid {
+        let mut grid = CollisionGrid::new(4, 4, Vec2::splat(TILE));
+        for y in 0..4 {
+            grid.set_solid(2, y, true);
+        }
+        grid
+    }
+
+    fn assert_close(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 0.01, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn tile_and_world_positions_round_trip() {
+        let grid = CollisionGrid::new(4, 4, Vec2::splat(TILE));
+        assert_eq!(grid.tile_to_world(IVec2::new(0, 0)), Vec2::new(-32.0, 32.0));
+        assert_eq!(grid.tile_to_world(IVec2::new(3, 3)), Vec2::new(16.0, -16.0));
+        for y in 0..4 {
+            for x in 0..4 {
+                let tile = IVec2::new(x, y);
+                let center = grid.tile_to_world(tile);
+                assert_eq!(grid.world_to_tile(center), tile);
+                // anywhere inside the tile maps back to it
+                assert_eq!(grid.world_to_tile(center + Vec2::new(7.9, -7.9)), tile);
+                assert_eq!(grid.world_to_tile(center - Vec2::new(7.9, -7.9)), tile);
+            }
+        }
+    }
+
+    #[test]
+    fn tiles_outside_the_map_are_solid() {
+        let grid = CollisionGrid::new(4, 4, Vec2::splat(TILE));
+        assert_eq!(grid.world_to_tile(Vec2::new(-60.0, 0.0)).x, -2);
+        assert!(grid.is_blocked(Vec2::new(-60.0, 0.0)));
+        assert!(grid.is_blocked(Vec2::new(0.0, 60.0)));
+        assert!(grid.is_solid(IVec2::new(4, 0)));
+        assert!(grid.is_solid(IVec2::new(0, -1)));
+        assert!(!grid.is_blocked(Vec2::ZERO));
+    }
+
+    #[test]
+    fn free_moves_go_the_whole_way() {
+        let grid = walled_grid();
+        let start = grid.tile_to_world(IVec2::new(0, 1));
+        let delta = Vec2::new(6.0, -20.0);
+        assert_close(grid.sweep_aabb(start, HALF_SIZE, delta), start + delta);
+    }
+
+    #[test]
+    fn blocked_moves_stop_against_the_wall() {
+        let grid = walled_grid();
+        let start = grid.tile_to_world(IVec2::new(0, 1));
+        let end = grid.sweep_aabb(start, HALF_SIZE, Vec2::new(40.0, 0.0));
+        // the wall starts at x = -8, the box is 8 wide
+        assert_close(end, Vec2::new(-12.0, start.y));
+        assert!(!grid.aabb_collides(end, HALF_SIZE));
+    }
+
+    #[test]
+    fn blocked_moves_slide_along_the_free_axis() {
+        let grid = walled_grid();
+        let start = grid.tile_to_world(IVec2::new(0, 1));
+        let end = grid.sweep_aabb(start, HALF_SIZE, Vec2::new(40.0, -10.0));
+        assert_close(end, Vec2::new(-12.0, start.y - 10.0));
+    }
+
+    #[test]
+    fn moves_stop_at_the_map_edge() {
+        let grid = walled_grid();
+        let start = grid.tile_to_world(IVec2::new(0, 1));
+        let end = grid.sweep_aabb(start, HALF_SIZE, Vec2::new(-20.0, 0.0));
+        // the map ends at x = -40
+        assert_close(end, Vec2::new(-36.0, start.y));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::prelude::*;
 use bevy::prelude::{Component, Entity};
 
+use super::tiled::TiledMapAsset;
+
+pub const DEFAULT_MAP_PATH: &str = "tiled_maps/gossip_farm_sim_v2.tmj";
+
 // Define your Map component
 #[derive(Component, Default, Debug)]
 pub struct TiledMap {
@@ -15,29 +19,92 @@ pub struct BasicTileCollider;
 #[derive(Component, Debug)]
 pub struct BasicPassiveTileCollider;
 
+// Tiles with an animation in their Tiled tileset (e.g. water)
 #[derive(Component, Default, Debug)]
-pub struct WaterSpriteAnimation {
+pub struct AnimatedTile {
     pub timer: Timer,
-    pub frame_count: usize,
+    pub frames: Vec<usize>,
     pub current_frame: usize,
-    pub base_index: usize,
 }
 
-pub struct CSVMapData {
-    pub csv_file_path: &'static str,
-    pub spritesheet_resource_name: &'static str,
-    pub collision_indices: Vec<i32>,
-    pub passive_collision_indices: Vec<i32>,
-    pub z_index: f32,
-    pub is_vertically_inverted: bool,
+// Which Tiled map to load, relative to `assets/`. Can be overridden with
+// `GOSSIP_MAP` to try out a new map without a rebuild
+#[derive(Resource, Debug, Clone)]
+pub struct MapSettings {
+    pub path: String,
+}
+
+impl Default for MapSettings {
+    fn default() -> Self {
+        Self {
+            path: std::env::var("GOSSIP_MAP").unwrap_or_else(|_| DEFAULT_MAP_PATH.to_string()),
+        }
+    }
 }
 
-pub struct NpcSpawnData {
-    pub csv_file_path: &'static str,
-    pub z_index: f32,
+#[derive(Resource, Debug)]
+pub struct TiledMapHandle(pub Handle<TiledMapAsset>);
+
+// Inserted once the map has been spawned
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LoadedMap {
+    pub width: usize,
+    pub height: usize,
+    pub tile_size: Vec2,
+    // keyed by the Tiled object type, e.g. "character_spawn"
+    pub spawn_points: HashMap<String, Vec<Vec3>>,
+    // tiles of the layers with a `landmark` property, keyed by its value,
+    // e.g. "food" or "bed"
+    pub landmarks: HashMap<String, Vec<IVec2>>,
 }
 
-pub struct PlayerSpawnData {
-    pub csv_file_path: &'static str,
-    pub z_index: f32,
+impl LoadedMap {
+    pub fn half_size(&self) -> Vec2 {
+        Vec2::new(
+            self.width as f32 * self.tile_size.x,
+            self.height as f32 * self.tile_size.y,
+        ) / 2.0
+    }
+
+    // world position of the center of the tile at column `x`, row `y`
+    pub fn tile_to_world(&self, x: usize, y: usize) -> Vec2 {
+        let half_size = self.half_size();
+        Vec2::new(
+            x as f32 * self.tile_size.x - half_size.x,
+            -(y as f32) * self.tile_size.y + half_size.y,
+        )
+    }
+
+    // Tiled object coordinates are in pixels from the top left of the map
+    pub fn pixel_to_world(&self, x: f32, y: f32) -> Vec2 {
+        let half_size = self.half_size();
+        Vec2::new(
+            x - self.tile_size.x / 2.0 - half_size.x,
+            -(y - self.tile_size.y / 2.0) + half_size.y,
+        )
+    }
+
+    // world space area covered by the map, tile edges included
+    pub fn world_bounds(&self) -> Rect {
+        let top_left = self.pixel_to_world(0.0, 0.0);
+        let bottom_right = self.pixel_to_world(
+            self.width as f32 * self.tile_size.x,
+            self.height as f32 * self.tile_size.y,
+        );
+        Rect::from_corners(top_left, bottom_right)
+    }
+
+    pub fn landmarks(&self, landmark: &str) -> &[IVec2] {
+        self.landmarks
+            .get(landmark)
+            .map(|tiles| tiles.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn spawn_points(&self, object_type: &str) -> &[Vec3] {
+        self.spawn_points
+            .get(object_type)
+            .map(|points| points.as_slice())
+            .unwrap_or(&[])
+    }
 }
@@ -0,0 +1,318 @@
+#[cfg(test)]
+use csv::{ReaderBuilder, Trim};
+use std::fmt;
+#[cfg(test)]
+use std::fs::File;
+#[cfg(test)]
+use std::io::Read;
+
+// Everything that can go wrong while reading a map or one of its layers.
+// Rows and columns are 1-based, like in a spreadsheet or Tiled's CSV export.
+#[derive(Debug)]
+pub enum MapLoadError {
+    Io {
+        path: String,
+        error: std::io::Error,
+    },
+    Csv {
+        path: String,
+        row: usize,
+        error: csv::Error,
+    },
+    InvalidCell {
+        path: String,
+        row: usize,
+        column: usize,
+        value: String,
+    },
+    RaggedRow {
+        path: String,
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    EmptyLayer {
+        path: String,
+    },
+    LayerSize {
+        path: String,
+        layer: String,
+        expected: usize,
+        found: usize,
+    },
+    Json {
+        path: String,
+        error: serde_json::Error,
+    },
+    ExternalTileset {
+        path: String,
+        source: String,
+    },
+    InvalidImagePath {
+        path: String,
+        image: String,
+    },
+}
+
+impl fmt::Display for MapLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapLoadError::Io { path, error } => {
+                write!(f, "{}: could not read file: {}", path, error)
+            }
+            MapLoadError::Csv { path, row, error } => {
+                write!(f, "{}:{}: malformed csv: {}", path, row, error)
+            }
+            MapLoadError::InvalidCell {
+                path,
+                row,
+                column,
+                value,
+            } => write!(
+                f,
+                "{}:{}:{}: expected a tile index, found {:?}",
+                path, row, column, value
+            ),
+            MapLoadError::RaggedRow {
+                path,
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}:{}: expected {} columns, found {}",
+                path, row, expected, found
+            ),
+            MapLoadError::EmptyLayer { path } => write!(f, "{}: layer has no rows", path),
+            MapLoadError::LayerSize {
+                path,
+                layer,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: layer {:?} should have {} tiles, found {}",
+                path, layer, expected, found
+            ),
+            MapLoadError::Json { path, error } => {
+                write!(f, "{}: could not parse map: {}", path, error)
+            }
+            MapLoadError::ExternalTileset { path, source } => write!(
+                f,
+                "{}: external tileset {:?} is not supported, embed it in the map",
+                path, source
+            ),
+            MapLoadError::InvalidImagePath { path, image } => {
+                write!(f, "{}: invalid tileset image path {:?}", path, image)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapLoadError {}
+
+// A rectangular layer of tile indices, `None` where the cell is empty
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MapLayerGrid {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Option<u32>>,
+}
+
+impl MapLayerGrid {
+    pub fn get(&self, x: usize, y: usize) -> Option<u32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells[y * self.width + x]
+    }
+
+    // (column, row, tile index) for every non-empty cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, u32)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, cell)| {
+                cell.map(|value| (index % self.width, index / self.width, value))
+            })
+    }
+
+    // Tiled layer data: one gid per cell, 0 for empty cells
+    pub fn from_tiled_data(
+        data: &[u32],
+        width: usize,
+        height: usize,
+        path: &str,
+        layer: &str,
+    ) -> Result<Self, MapLoadError> {
+        if data.len() != width * height {
+            return Err(MapLoadError::LayerSize {
+                path: path.to_string(),
+                layer: layer.to_string(),
+                expected: width * height,
+                found: data.len(),
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells: data
+                .iter()
+                .map(|gid| if *gid == 0 { None } else { Some(*gid) })
+                .collect(),
+        })
+    }
+
+    // Tiled's CSV layer export: no header row, -1 for empty cells. Maps load
+    // from Tiled json now, the csv path is kept for the layer fixtures
+    #[cfg(test)]
+    pub fn from_csv_reader(reader: impl Read, path: &str) -> Result<Self, MapLoadError> {
+        let mut csv_reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(Trim::All)
+            .from_reader(reader);
+
+        let mut grid = MapLayerGrid::default();
+
+        for (row_index, record) in csv_reader.records().enumerate() {
+            let row = row_index + 1;
+            let record = record.map_err(|error| MapLoadError::Csv {
+                path: path.to_string(),
+                row,
+                error,
+            })?;
+
+            // Tiled ends every row but the last with a comma
+            let cells: Vec<&str> = match record.iter().next_back() {
+                Some("") => record.iter().take(record.len() - 1).collect(),
+                _ => record.iter().collect(),
+            };
+
+            if row_index == 0 {
+                grid.width = cells.len();
+            } else if cells.len() != grid.width {
+                return Err(MapLoadError::RaggedRow {
+                    path: path.to_string(),
+                    row,
+                    expected: grid.width,
+                    found: cells.len(),
+                });
+            }
+
+            for (column_index, cell) in cells.iter().enumerate() {
+                let value = cell
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|value| *value >= -1 && *value <= u32::MAX as i64)
+                    .ok_or_else(|| MapLoadError::InvalidCell {
+                        path: path.to_string(),
+                        row,
+                        column: column_index + 1,
+                        value: cell.to_string(),
+                    })?;
+                grid.cells.push(if value == -1 {
+                    None
+                } else {
+                    Some(value as u32)
+                });
+            }
+
+            grid.height += 1;
+        }
+
+        if grid.height == 0 || grid.width == 0 {
+            return Err(MapLoadError::EmptyLayer {
+                path: path.to_string(),
+            });
+        }
+
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+pub fn read_csv_layer(path: &str) -> Result<MapLayerGrid, MapLoadError> {
+    let file = File::open(path).map_err(|error| MapLoadError::Io {
+        path: path.to_string(),
+        error,
+    })?;
+    MapLayerGrid::from_csv_reader(file, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/tiled_maps/csv");
+
+    #[test]
+    fn every_map_csv_keeps_its_first_row() {
+        let mut paths: Vec<_> = std::fs::read_dir(CSV_DIR)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty(), "no csv layers in {}", CSV_DIR);
+
+        for path in paths {
+            let path = path.to_str().unwrap();
+            let grid = read_csv_layer(path).unwrap_or_else(|error| panic!("{}", error));
+            assert_eq!((grid.width, grid.height), (100, 60), "{}", path);
+            assert_eq!(grid.cells.len(), 100 * 60, "{}", path);
+        }
+    }
+
+    #[test]
+    fn first_row_cells_are_read() {
+        let grid = MapLayerGrid::from_csv_reader("3,-1,\n-1,4".as_bytes(), "test.csv").unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.get(0, 0), Some(3));
+        assert_eq!(grid.get(1, 0), None);
+        assert_eq!(grid.get(1, 1), Some(4));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 0, 3), (1, 1, 4)]);
+    }
+
+    #[test]
+    fn invalid_cell_reports_row_and_column() {
+        let error =
+            MapLayerGrid::from_csv_reader("1,2,3,\n4,x,6".as_bytes(), "test.csv").unwrap_err();
+        match error {
+            MapLoadError::InvalidCell {
+                row, column, value, ..
+            } => assert_eq!((row, column, value.as_str()), (2, 2, "x")),
+            other => panic!("expected InvalidCell, got {:?}", other),
+        }
+        assert_eq!(
+            MapLayerGrid::from_csv_reader("-2".as_bytes(), "test.csv")
+                .unwrap_err()
+                .to_string(),
+            "test.csv:1:1: expected a tile index, found \"-2\""
+        );
+    }
+
+    #[test]
+    fn ragged_row_reports_row_and_widths() {
+        let error = MapLayerGrid::from_csv_reader("1,2,3,\n4,5,6,\n7,8".as_bytes(), "test.csv")
+            .unwrap_err();
+        match error {
+            MapLoadError::RaggedRow {
+                row,
+                expected,
+                found,
+                ..
+            } => assert_eq!((row, expected, found), (3, 3, 2)),
+            other => panic!("expected RaggedRow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn empty_input_is_an_empty_layer() {
+        assert!(matches!(
+            MapLayerGrid::from_csv_reader("".as_bytes(), "test.csv"),
+            Err(MapLoadError::EmptyLayer { .. })
+        ));
+    }
+}
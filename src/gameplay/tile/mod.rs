@@ -1,15 +1,36 @@
 use crate::prelude::*;
 
+use super::components::FarmSet;
+
 pub mod systems;
-use systems::{spawn_map_from_csv, spawn_water, water_sprite_animation};
+use systems::{animated_tile_system, load_tiled_map, spawn_tiled_map};
 
 pub mod components;
 
+pub mod collision;
+
+pub mod map_layer;
+use components::{LoadedMap, MapSettings};
+
+pub mod tiled;
+use tiled::{TiledMapAsset, TiledMapLoader};
+
 pub struct TileMapPlugin;
 
 impl Plugin for TileMapPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_systems((spawn_water, spawn_map_from_csv))
-            .add_system(water_sprite_animation);
+        app.init_asset::<TiledMapAsset>()
+            .init_asset_loader::<TiledMapLoader>()
+            .init_resource::<MapSettings>()
+            .add_systems(Startup, load_tiled_map)
+            .add_systems(
+                Update,
+                (
+                    spawn_tiled_map.run_if(not(resource_exists::<LoadedMap>)),
+                    animated_tile_system,
+                )
+                    .chain()
+                    .in_set(FarmSet::Load),
+            );
     }
 }
@@ -0,0 +1,248 @@
+// Tiled JSON (.tmj) map format
+// https://doc.mapeditor.org/en/stable/reference/json-map-format/
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::reflect::TypePath;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+use super::map_layer::{MapLayerGrid, MapLoadError};
+
+// gid bits Tiled uses to flag flipped tiles
+const TILED_FLIP_FLAGS: u32 = 0xE0000000;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledProperty {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub property_type: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledFrame {
+    pub tileid: u32,
+    pub duration: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledTile {
+    pub id: u32,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>,
+    #[serde(default)]
+    pub animation: Vec<TiledFrame>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledTileset {
+    pub firstgid: u32,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub image: String,
+    #[serde(default)]
+    pub columns: u32,
+    #[serde(default)]
+    pub tilecount: u32,
+    #[serde(default)]
+    pub tilewidth: u32,
+    #[serde(default)]
+    pub tileheight: u32,
+    #[serde(default)]
+    pub margin: u32,
+    #[serde(default)]
+    pub spacing: u32,
+    #[serde(default)]
+    pub tiles: Vec<TiledTile>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledObject {
+    pub id: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type", alias = "class", default)]
+    pub object_type: String,
+    pub x: f32,
+    pub y: f32,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledTileLayer {
+    pub name: String,
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u32>,
+    // `data` checked against the layer size, filled in by the loader
+    #[serde(skip)]
+    pub grid: MapLayerGrid,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledObjectGroup {
+    pub name: String,
+    #[serde(default)]
+    pub objects: Vec<TiledObject>,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TiledLayer {
+    TileLayer(TiledTileLayer),
+    ObjectGroup(TiledObjectGroup),
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TiledMapJson {
+    pub width: usize,
+    pub height: usize,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    pub tilesets: Vec<TiledTileset>,
+    pub layers: Vec<TiledLayer>,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+pub fn find_property<'a>(properties: &'a [TiledProperty], name: &str) -> Option<&'a TiledProperty> {
+    properties.iter().find(|property| property.name == name)
+}
+
+pub fn property_f32(properties: &[TiledProperty], name: &str) -> Option<f32> {
+    find_property(properties, name)
+        .and_then(|property| property.value.as_f64())
+        .map(|value| value as f32)
+}
+
+pub fn property_str<'a>(properties: &'a [TiledProperty], name: &str) -> Option<&'a str> {
+    find_property(properties, name).and_then(|property| property.value.as_str())
+}
+
+pub fn property_bool(properties: &[TiledProperty], name: &str) -> bool {
+    find_property(properties, name)
+        .and_then(|property| property.value.as_bool())
+        .unwrap_or(false)
+}
+
+impl TiledTileset {
+    pub fn contains(&self, gid: u32) -> bool {
+        gid >= self.firstgid && gid < self.firstgid + self.tilecount
+    }
+
+    pub fn tile(&self, local_id: u32) -> Option<&TiledTile> {
+        self.tiles.iter().find(|tile| tile.id == local_id)
+    }
+}
+
+impl TiledMapJson {
+    // Returns the tileset index and the tile index local to that tileset,
+    // `None` for empty cells
+    pub fn resolve_gid(&self, gid: u32) -> Option<(usize, u32)> {
+        let gid = gid & !TILED_FLIP_FLAGS;
+        if gid == 0 {
+            return None;
+        }
+
+        self.tilesets
+            .iter()
+            .position(|tileset| tileset.contains(gid))
+            .map(|index| (index, gid - self.tilesets[index].firstgid))
+    }
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct TiledMapAsset {
+    pub map: TiledMapJson,
+    // one image per tileset, same order as `map.tilesets`
+    pub tileset_images: Vec<Handle<Image>>,
+}
+
+#[derive(Default)]
+pub struct TiledMapLoader;
+
+impl AssetLoader for TiledMapLoader {
+    type Asset = TiledMapAsset;
+    type Settings = ();
+    type Error = MapLoadError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<TiledMapAsset, MapLoadError>> {
+        Box::pin(async move {
+            let path = load_context.path().display().to_string();
+
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(|error| MapLoadError::Io {
+                    path: path.clone(),
+                    error,
+                })?;
+            let mut map: TiledMapJson =
+                serde_json::from_slice(&bytes).map_err(|error| MapLoadError::Json {
+                    path: path.clone(),
+                    error,
+                })?;
+
+            for layer in map.layers.iter_mut() {
+                if let TiledLayer::TileLayer(tile_layer) = layer {
+                    tile_layer.grid = MapLayerGrid::from_tiled_data(
+                        &tile_layer.data,
+                        tile_layer.width,
+                        tile_layer.height,
+                        &path,
+                        &tile_layer.name,
+                    )?;
+                }
+            }
+
+            let mut tileset_images = Vec::new();
+            for tileset in map.tilesets.iter() {
+                if let Some(source) = &tileset.source {
+                    return Err(MapLoadError::ExternalTileset {
+                        path,
+                        source: source.clone(),
+                    });
+                }
+                let image_path = load_context
+                    .asset_path()
+                    .resolve_embed(&tileset.image)
+                    .map_err(|_| MapLoadError::InvalidImagePath {
+                        path: path.clone(),
+                        image: tileset.image.clone(),
+                    })?;
+                tileset_images.push(load_context.load(image_path));
+            }
+
+            Ok(TiledMapAsset {
+                map,
+                tileset_images,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmj"]
+    }
+}
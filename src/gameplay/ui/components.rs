@@ -0,0 +1,124 @@
+use crate::prelude::*;
+
+use crate::gameplay::dialogue::components::DialogueChoice;
+use crate::gameplay::state::components::SimulationSpeed;
+
+// relative to `assets/`
+pub const PLAY_BUTTON_IMAGE_PATH: &str = "user_interface/basic_play_button.png";
+
+// the play button png is 96x32, drawn at 3x like the rest of the pixel art
+pub const PLAY_BUTTON_SIZE: Vec2 = Vec2::new(288.0, 96.0);
+
+pub const SCREEN_BACKGROUND_COLOR: Color = Color::rgb(0.11, 0.1, 0.16);
+pub const OVERLAY_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+pub const PANEL_BACKGROUND_COLOR: Color = Color::rgba(0.16, 0.14, 0.22, 0.95);
+pub const TEXT_COLOR: Color = Color::rgb(0.95, 0.92, 0.85);
+pub const BUTTON_NORMAL_COLOR: Color = Color::rgb(0.27, 0.24, 0.36);
+pub const BUTTON_HOVERED_COLOR: Color = Color::rgb(0.36, 0.32, 0.48);
+pub const BUTTON_PRESSED_COLOR: Color = Color::rgb(0.2, 0.18, 0.28);
+// image buttons are tinted instead of filled
+pub const IMAGE_BUTTON_NORMAL_TINT: Color = Color::WHITE;
+pub const IMAGE_BUTTON_HOVERED_TINT: Color = Color::rgb(0.85, 0.85, 0.85);
+pub const IMAGE_BUTTON_PRESSED_TINT: Color = Color::rgb(0.65, 0.65, 0.65);
+
+#[derive(Resource, Debug)]
+pub struct UiAssets {
+    pub play_button: Handle<Image>,
+}
+
+// root node of each screen, everything under it is despawned when the state
+// is left
+#[derive(Component, Debug)]
+pub struct MainMenuScreen;
+
+#[derive(Component, Debug)]
+pub struct LoadingScreen;
+
+#[derive(Component, Debug)]
+pub struct PauseScreen;
+
+#[derive(Component, Debug)]
+pub struct MainDialogueScreen;
+
+#[derive(Component, Debug)]
+pub struct SideDialogueScreen;
+
+// stays up from the end of loading until the main menu
+#[derive(Component, Debug)]
+pub struct HudScreen;
+
+#[derive(Component, Debug)]
+pub struct LoadingProgressText;
+
+#[derive(Component, Debug)]
+pub struct ClockText;
+
+#[derive(Component, Debug)]
+pub struct SimulationSpeedText;
+
+#[derive(Component, Debug)]
+pub struct TranscriptText;
+
+#[derive(Component, Debug)]
+pub struct DialogueLineText;
+
+#[derive(Component, Debug)]
+pub struct DialogueChoiceButton(pub DialogueChoice);
+
+pub const TEXT_REVEAL_CHARS_PER_SECOND: f32 = 45.0;
+
+// The pair whose conversation the side dialogue shows, and how much of it
+// has been revealed so far
+#[derive(Resource, Debug, Clone)]
+pub struct SideDialogueConversation {
+    pub entity_a_uuid: String,
+    pub entity_b_uuid: String,
+    pub revealed_chars: f32,
+}
+
+impl SideDialogueConversation {
+    pub fn new(entity_a_uuid: String, entity_b_uuid: String) -> Self {
+        Self {
+            entity_a_uuid,
+            entity_b_uuid,
+            revealed_chars: 0.0,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuButton {
+    Play,
+    Resume,
+    SaveGame,
+    LoadGame,
+    MainMenu,
+    CloseDialogue,
+    TogglePause,
+    StepTick,
+    Speed(SimulationSpeed),
+}
+
+impl MenuButton {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuButton::Play => "Play",
+            MenuButton::Resume => "Resume",
+            MenuButton::SaveGame => "Save (F5)",
+            MenuButton::LoadGame => "Load (F9)",
+            MenuButton::MainMenu => "Main Menu",
+            MenuButton::CloseDialogue => "Close",
+            MenuButton::TogglePause => "||",
+            MenuButton::StepTick => ">|",
+            MenuButton::Speed(speed) => speed.label(),
+        }
+    }
+}
+
+// buttons drawn from an image rather than a filled box
+#[derive(Component, Debug)]
+pub struct ImageButton;
+
+// full screen overlay the time of day tints the farm with
+#[derive(Component, Debug)]
+pub struct SceneTint;
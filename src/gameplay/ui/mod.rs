@@ -0,0 +1,65 @@
+use crate::prelude::*;
+
+use super::clock::components::GameClock;
+use super::state::components::{GameState, SimulationSpeed};
+
+mod systems;
+use systems::{
+    despawn_screen, dialogue_choice_buttons, end_side_dialogue, load_ui_assets,
+    menu_button_actions, menu_button_colors, reveal_side_dialogue_transcript, spawn_hud,
+    spawn_loading_screen, spawn_main_dialogue, spawn_main_menu, spawn_pause_menu, spawn_scene_tint,
+    spawn_side_dialogue, update_clock_text, update_loading_screen, update_main_dialogue_panel,
+    update_scene_tint, update_simulation_speed_text,
+};
+
+pub mod components;
+use components::{
+    HudScreen, LoadingScreen, MainDialogueScreen, MainMenuScreen, PauseScreen, SideDialogueScreen,
+};
+
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_ui_assets)
+            .add_systems(Startup, spawn_scene_tint)
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(
+                OnExit(GameState::MainMenu),
+                despawn_screen::<MainMenuScreen>,
+            )
+            .add_systems(OnEnter(GameState::Loading), spawn_loading_screen)
+            .add_systems(
+                OnExit(GameState::Loading),
+                (despawn_screen::<LoadingScreen>, spawn_hud),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), despawn_screen::<HudScreen>)
+            .add_systems(OnEnter(GameState::Pause), spawn_pause_menu)
+            .add_systems(OnExit(GameState::Pause), despawn_screen::<PauseScreen>)
+            .add_systems(OnEnter(GameState::MainDialogue), spawn_main_dialogue)
+            .add_systems(
+                OnExit(GameState::MainDialogue),
+                despawn_screen::<MainDialogueScreen>,
+            )
+            .add_systems(OnEnter(GameState::SideDialogue), spawn_side_dialogue)
+            .add_systems(
+                OnExit(GameState::SideDialogue),
+                (despawn_screen::<SideDialogueScreen>, end_side_dialogue),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_loading_screen.run_if(in_state(GameState::Loading)),
+                    reveal_side_dialogue_transcript.run_if(in_state(GameState::SideDialogue)),
+                    (update_main_dialogue_panel, dialogue_choice_buttons)
+                        .run_if(in_state(GameState::MainDialogue)),
+                    (update_clock_text, update_scene_tint).run_if(resource_changed::<GameClock>),
+                    update_simulation_speed_text.run_if(
+                        resource_changed::<SimulationSpeed>.or_else(state_changed::<GameState>),
+                    ),
+                    menu_button_colors,
+                    menu_button_actions,
+                ),
+            );
+    }
+}
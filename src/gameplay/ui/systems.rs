@@ -0,0 +1,617 @@
+use bevy::asset::{LoadState, UntypedAssetId};
+
+use crate::prelude::*;
+
+// components
+use crate::gameplay::clock::components::GameClock;
+use crate::gameplay::dialogue::components::{DialogueChoice, MainDialogueConversation};
+use crate::gameplay::player::components::{EtherPet, PlayerData, PlayerUniqueId};
+use crate::gameplay::spritesheet::components::{SpriteSheetManifestHandle, SpriteSheets};
+use crate::gameplay::state::components::{CameraState, GameState, SimulationSpeed};
+use crate::gameplay::tile::components::{LoadedMap, TiledMapHandle};
+use crate::gameplay::ui::components::{
+    ClockText, DialogueChoiceButton, DialogueLineText, HudScreen, ImageButton, LoadingProgressText,
+    LoadingScreen, MainDialogueScreen, MainMenuScreen, MenuButton, PauseScreen, SceneTint,
+    SideDialogueConversation, SideDialogueScreen, SimulationSpeedText, TranscriptText, UiAssets,
+    BUTTON_HOVERED_COLOR, BUTTON_NORMAL_COLOR, BUTTON_PRESSED_COLOR, IMAGE_BUTTON_HOVERED_TINT,
+    IMAGE_BUTTON_NORMAL_TINT, IMAGE_BUTTON_PRESSED_TINT, OVERLAY_BACKGROUND_COLOR,
+    PANEL_BACKGROUND_COLOR, PLAY_BUTTON_IMAGE_PATH, PLAY_BUTTON_SIZE, SCREEN_BACKGROUND_COLOR,
+    TEXT_COLOR, TEXT_REVEAL_CHARS_PER_SECOND,
+};
+use crate::http_request::components::ConversationHistoryResource;
+
+// events
+use crate::gameplay::dialogue::events::DialogueChoiceEvent;
+use crate::gameplay::save::events::{LoadGameEvent, SaveGameEvent};
+use crate::gameplay::state::events::StepSimulationEvent;
+
+pub fn load_ui_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(UiAssets {
+        play_button: asset_server.load(PLAY_BUTTON_IMAGE_PATH),
+    });
+}
+
+fn text(value: impl Into<String>, font_size: f32) -> TextBundle {
+    TextBundle::from_section(
+        value,
+        TextStyle {
+            font_size,
+            color: TEXT_COLOR,
+            ..default()
+        },
+    )
+}
+
+fn spawn_text_button(parent: &mut ChildBuilder, label: &str, width: Val, marker: impl Component) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                width,
+                min_height: Val::Px(48.0),
+                margin: UiRect::all(Val::Px(6.0)),
+                padding: UiRect::horizontal(Val::Px(12.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: BUTTON_NORMAL_COLOR.into(),
+            ..default()
+        })
+        .insert(marker)
+        .with_children(|button| {
+            button.spawn(text(label, 24.0));
+        });
+}
+
+fn spawn_menu_button(parent: &mut ChildBuilder, menu_button: MenuButton) {
+    spawn_text_button(parent, menu_button.label(), Val::Px(220.0), menu_button);
+}
+
+// a full screen node, centered column layout
+fn screen_root(background_color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        background_color: background_color.into(),
+        ..default()
+    }
+}
+
+pub fn spawn_main_menu(mut commands: Commands, ui_assets: Res<UiAssets>) {
+    commands
+        .spawn(screen_root(SCREEN_BACKGROUND_COLOR))
+        .insert(Name::new("Main Menu Screen"))
+        .insert(MainMenuScreen)
+        .with_children(|parent| {
+            parent.spawn(text("Gossip Farm", 64.0).with_style(Style {
+                margin: UiRect::bottom(Val::Px(48.0)),
+                ..default()
+            }));
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        width: Val::Px(PLAY_BUTTON_SIZE.x),
+                        height: Val::Px(PLAY_BUTTON_SIZE.y),
+                        ..default()
+                    },
+                    image: UiImage::new(ui_assets.play_button.clone()),
+                    background_color: IMAGE_BUTTON_NORMAL_TINT.into(),
+                    ..default()
+                })
+                .insert(MenuButton::Play)
+                .insert(ImageButton);
+            parent.spawn(text("press Enter to play", 18.0).with_style(Style {
+                margin: UiRect::top(Val::Px(24.0)),
+                ..default()
+            }));
+        });
+}
+
+pub fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn(screen_root(SCREEN_BACKGROUND_COLOR))
+        .insert(Name::new("Loading Screen"))
+        .insert(LoadingScreen)
+        .with_children(|parent| {
+            parent.spawn(text("Loading...", 40.0));
+            parent
+                .spawn(text("", 20.0).with_style(Style {
+                    margin: UiRect::top(Val::Px(16.0)),
+                    ..default()
+                }))
+                .insert(LoadingProgressText);
+        });
+}
+
+pub fn spawn_hud(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    simulation_speed: Res<SimulationSpeed>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(12.0),
+                right: Val::Px(12.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                ..default()
+            },
+            background_color: PANEL_BACKGROUND_COLOR.into(),
+            // above the pause overlay, so the time controls work while paused
+            z_index: ZIndex::Global(1),
+            ..default()
+        })
+        .insert(Name::new("Hud"))
+        .insert(HudScreen)
+        .with_children(|parent| {
+            parent.spawn(text(clock.label(), 20.0)).insert(ClockText);
+            parent
+                .spawn(text(simulation_speed.label(), 20.0))
+                .insert(SimulationSpeedText);
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for menu_button in [
+                        MenuButton::TogglePause,
+                        MenuButton::StepTick,
+                        MenuButton::Speed(SimulationSpeed::Normal),
+                        MenuButton::Speed(SimulationSpeed::Double),
+                        MenuButton::Speed(SimulationSpeed::FastForward),
+                    ] {
+                        spawn_text_button(row, menu_button.label(), Val::Px(56.0), menu_button);
+                    }
+                });
+        });
+}
+
+// "Paused" while GameState::Pause, the speed otherwise
+pub fn update_simulation_speed_text(
+    simulation_speed: Res<SimulationSpeed>,
+    app_state: Res<State<GameState>>,
+    mut simulation_speed_text_query: Query<&mut Text, With<SimulationSpeedText>>,
+) {
+    let label = match app_state.get() {
+        GameState::Pause => "Paused",
+        _ => simulation_speed.label(),
+    };
+    for mut simulation_speed_text in simulation_speed_text_query.iter_mut() {
+        if simulation_speed_text.sections[0].value != label {
+            simulation_speed_text.sections[0].value = label.to_string();
+        }
+    }
+}
+
+// the label only changes once a game minute, most frames leave the text alone
+pub fn update_clock_text(
+    clock: Res<GameClock>,
+    mut clock_text_query: Query<&mut Text, With<ClockText>>,
+) {
+    let label = clock.label();
+    for mut clock_text in clock_text_query.iter_mut() {
+        if clock_text.sections[0].value != label {
+            clock_text.sections[0].value = label.clone();
+        }
+    }
+}
+
+// drawn under every other UI node but over the farm
+pub fn spawn_scene_tint(mut commands: Commands, clock: Res<GameClock>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: clock.tint().into(),
+            z_index: ZIndex::Global(-1),
+            ..default()
+        })
+        .insert(Name::new("Scene Tint"))
+        .insert(SceneTint);
+}
+
+pub fn update_scene_tint(
+    clock: Res<GameClock>,
+    mut tint_query: Query<&mut BackgroundColor, With<SceneTint>>,
+) {
+    for mut background_color in tint_query.iter_mut() {
+        background_color.0 = clock.tint();
+    }
+}
+
+pub fn spawn_pause_menu(mut commands: Commands) {
+    commands
+        .spawn(screen_root(OVERLAY_BACKGROUND_COLOR))
+        .insert(Name::new("Pause Screen"))
+        .insert(PauseScreen)
+        .with_children(|parent| {
+            parent.spawn(text("Paused", 48.0).with_style(Style {
+                margin: UiRect::bottom(Val::Px(32.0)),
+                ..default()
+            }));
+            for menu_button in [
+                MenuButton::Resume,
+                MenuButton::SaveGame,
+                MenuButton::LoadGame,
+                MenuButton::MainMenu,
+            ] {
+                spawn_menu_button(parent, menu_button);
+            }
+        });
+}
+
+fn spawn_dialogue_panel(commands: &mut Commands, name: &str, title: &str, style: Style) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                ..style
+            },
+            background_color: PANEL_BACKGROUND_COLOR.into(),
+            ..default()
+        })
+        .insert(Name::new(name.to_string()))
+        .with_children(|parent| {
+            parent.spawn(text(title, 28.0).with_style(Style {
+                margin: UiRect::bottom(Val::Px(12.0)),
+                ..default()
+            }));
+        })
+        .id()
+}
+
+// the conversation with the active pet, across the bottom of the screen
+pub fn spawn_main_dialogue(
+    mut commands: Commands,
+    main_dialogue_conversation: Option<Res<MainDialogueConversation>>,
+) {
+    let title = main_dialogue_conversation
+        .as_ref()
+        .map_or("Conversation".to_string(), |conversation| {
+            conversation.partner_name.clone()
+        });
+
+    let panel = spawn_dialogue_panel(
+        &mut commands,
+        "Main Dialogue Screen",
+        &title,
+        Style {
+            left: Val::Percent(10.0),
+            right: Val::Percent(10.0),
+            bottom: Val::Px(24.0),
+            min_height: Val::Percent(25.0),
+            ..default()
+        },
+    );
+    commands
+        .entity(panel)
+        .insert(MainDialogueScreen)
+        .with_children(|parent| {
+            parent
+                .spawn(text("", 22.0).with_style(Style {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                }))
+                .insert(DialogueLineText);
+            for choice in DialogueChoice::ALL {
+                spawn_text_button(
+                    parent,
+                    choice.text(),
+                    Val::Percent(100.0),
+                    DialogueChoiceButton(choice),
+                );
+            }
+            spawn_menu_button(parent, MenuButton::CloseDialogue);
+        });
+}
+
+// types out whatever the partner just said, the choices go away once one
+// has been answered. Text keeps wall-clock pace whatever the simulation speed
+pub fn update_main_dialogue_panel(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    main_dialogue_conversation: Option<ResMut<MainDialogueConversation>>,
+    mut dialogue_line_text_query: Query<&mut Text, With<DialogueLineText>>,
+    choice_buttons_query: Query<Entity, With<DialogueChoiceButton>>,
+) {
+    let Some(mut conversation) = main_dialogue_conversation else {
+        return;
+    };
+
+    let total_chars = conversation.partner_line.chars().count();
+    conversation.revealed_chars = (conversation.revealed_chars
+        + TEXT_REVEAL_CHARS_PER_SECOND * time.delta_seconds())
+    .min(total_chars as f32);
+    let revealed: String = conversation
+        .partner_line
+        .chars()
+        .take(conversation.revealed_chars as usize)
+        .collect();
+
+    for mut dialogue_line_text in dialogue_line_text_query.iter_mut() {
+        if dialogue_line_text.sections[0].value != revealed {
+            dialogue_line_text.sections[0].value = revealed.clone();
+        }
+    }
+
+    if conversation.choice.is_some() {
+        for choice_button_entity in choice_buttons_query.iter() {
+            commands.entity(choice_button_entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn dialogue_choice_buttons(
+    button_query: Query<(&Interaction, &DialogueChoiceButton), Changed<Interaction>>,
+    mut dialogue_choice_event_writer: EventWriter<DialogueChoiceEvent>,
+) {
+    for (interaction, choice_button) in button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            dialogue_choice_event_writer.send(DialogueChoiceEvent {
+                choice: choice_button.0,
+            });
+        }
+    }
+}
+
+// a conversation between two other pets, down the right side of the screen
+pub fn spawn_side_dialogue(
+    mut commands: Commands,
+    side_dialogue_conversation: Option<Res<SideDialogueConversation>>,
+    pets_query: Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+) {
+    let title = match side_dialogue_conversation {
+        Some(conversation) => format!(
+            "{} & {}",
+            pet_name(&pets_query, &conversation.entity_a_uuid),
+            pet_name(&pets_query, &conversation.entity_b_uuid)
+        ),
+        None => "Overheard".to_string(),
+    };
+
+    let panel = spawn_dialogue_panel(
+        &mut commands,
+        "Side Dialogue Screen",
+        &title,
+        Style {
+            top: Val::Px(24.0),
+            right: Val::Px(24.0),
+            bottom: Val::Px(24.0),
+            width: Val::Percent(30.0),
+            ..default()
+        },
+    );
+    commands
+        .entity(panel)
+        .insert(SideDialogueScreen)
+        .with_children(|parent| {
+            parent
+                .spawn(text("", 20.0).with_style(Style {
+                    flex_grow: 1.0,
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                }))
+                .insert(TranscriptText);
+            spawn_menu_button(parent, MenuButton::CloseDialogue);
+        });
+}
+
+fn pet_name(
+    pets_query: &Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+    uuid: &str,
+) -> String {
+    pets_query
+        .iter()
+        .find(|(unique_id, _)| unique_id.uuid == uuid)
+        .map(|(_, player_data)| player_data.name.clone())
+        .unwrap_or_else(|| uuid.to_string())
+}
+
+// Types the transcript out a few characters at a time. Rebuilt every frame
+// so a reply that is still in flight shows up once it arrives
+pub fn reveal_side_dialogue_transcript(
+    time: Res<Time<Real>>,
+    conversation_history: Res<ConversationHistoryResource>,
+    side_dialogue_conversation: Option<ResMut<SideDialogueConversation>>,
+    pets_query: Query<(&PlayerUniqueId, &PlayerData), With<EtherPet>>,
+    mut transcript_text_query: Query<&mut Text, With<TranscriptText>>,
+) {
+    let Some(mut side_dialogue_conversation) = side_dialogue_conversation else {
+        return;
+    };
+
+    let transcript = match conversation_history.latest_between(
+        &side_dialogue_conversation.entity_a_uuid,
+        &side_dialogue_conversation.entity_b_uuid,
+    ) {
+        Some(response) if !response.conversation.is_empty() => response
+            .conversation
+            .iter()
+            .map(|line| {
+                format!(
+                    "{}: {}",
+                    pet_name(&pets_query, &line.speaker_uuid),
+                    line.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => "They are still talking...".to_string(),
+    };
+
+    let total_chars = transcript.chars().count();
+    side_dialogue_conversation.revealed_chars = (side_dialogue_conversation.revealed_chars
+        + TEXT_REVEAL_CHARS_PER_SECOND * time.delta_seconds())
+    .min(total_chars as f32);
+    let revealed: String = transcript
+        .chars()
+        .take(side_dialogue_conversation.revealed_chars as usize)
+        .collect();
+
+    for mut transcript_text in transcript_text_query.iter_mut() {
+        if transcript_text.sections[0].value != revealed {
+            transcript_text.sections[0].value = revealed.clone();
+        }
+    }
+}
+
+pub fn end_side_dialogue(mut commands: Commands) {
+    commands.remove_resource::<SideDialogueConversation>();
+}
+
+pub fn despawn_screen<T: Component>(mut commands: Commands, screen_query: Query<Entity, With<T>>) {
+    for screen_entity in screen_query.iter() {
+        commands.entity(screen_entity).despawn_recursive();
+    }
+}
+
+// Waits on the spritesheet manifest, the map and everything they depend on,
+// then on the sheets being registered and the map being spawned
+pub fn update_loading_screen(
+    asset_server: Res<AssetServer>,
+    sprite_sheets: Res<SpriteSheets>,
+    manifest_handle: Option<Res<SpriteSheetManifestHandle>>,
+    map_handle: Option<Res<TiledMapHandle>>,
+    loaded_map: Option<Res<LoadedMap>>,
+    mut progress_text_query: Query<&mut Text, With<LoadingProgressText>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_camera_state: ResMut<NextState<CameraState>>,
+) {
+    let mut asset_ids: Vec<UntypedAssetId> = Vec::new();
+    if let Some(manifest_handle) = manifest_handle.as_ref() {
+        asset_ids.push(manifest_handle.0.id().untyped());
+    }
+    asset_ids.extend(
+        sprite_sheets
+            .sheets
+            .values()
+            .map(|sheet| sheet.texture.id().untyped()),
+    );
+    if let Some(map_handle) = map_handle.as_ref() {
+        asset_ids.push(map_handle.0.id().untyped());
+    }
+
+    if let Some(failed_id) = asset_ids
+        .iter()
+        .find(|id| matches!(asset_server.get_load_state(**id), Some(LoadState::Failed)))
+    {
+        let path = asset_server.get_path(*failed_id);
+        for mut progress_text in progress_text_query.iter_mut() {
+            progress_text.sections[0].value = format!("Failed to load {:?}", path);
+        }
+        return;
+    }
+
+    let steps = asset_ids
+        .iter()
+        .map(|id| asset_server.is_loaded_with_dependencies(*id))
+        .chain([
+            manifest_handle.is_some(),
+            map_handle.is_some(),
+            sprite_sheets.loaded,
+            loaded_map.is_some(),
+        ]);
+    let (ready, total) = steps.fold((0, 0), |(ready, total), done| {
+        (ready + done as usize, total + 1)
+    });
+
+    for mut progress_text in progress_text_query.iter_mut() {
+        progress_text.sections[0].value = format!("{} / {}", ready, total);
+    }
+
+    if ready == total {
+        next_camera_state.set(CameraState::ManualCameraControl);
+        next_game_state.set(GameState::Playing);
+    }
+}
+
+pub fn menu_button_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, Option<&ImageButton>),
+        (
+            Changed<Interaction>,
+            Or<(With<MenuButton>, With<DialogueChoiceButton>)>,
+        ),
+    >,
+) {
+    for (interaction, mut background_color, image_button) in button_query.iter_mut() {
+        let color = match (interaction, image_button.is_some()) {
+            (Interaction::Pressed, false) => BUTTON_PRESSED_COLOR,
+            (Interaction::Hovered, false) => BUTTON_HOVERED_COLOR,
+            (Interaction::None, false) => BUTTON_NORMAL_COLOR,
+            (Interaction::Pressed, true) => IMAGE_BUTTON_PRESSED_TINT,
+            (Interaction::Hovered, true) => IMAGE_BUTTON_HOVERED_TINT,
+            (Interaction::None, true) => IMAGE_BUTTON_NORMAL_TINT,
+        };
+        *background_color = color.into();
+    }
+}
+
+pub fn menu_button_actions(
+    button_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_camera_state: ResMut<NextState<CameraState>>,
+    mut save_game_event_writer: EventWriter<SaveGameEvent>,
+    mut load_game_event_writer: EventWriter<LoadGameEvent>,
+    mut step_simulation_event_writer: EventWriter<StepSimulationEvent>,
+    mut simulation_speed: ResMut<SimulationSpeed>,
+    app_state: Res<State<GameState>>,
+) {
+    for (interaction, menu_button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match menu_button {
+            MenuButton::Play => next_game_state.set(GameState::Loading),
+            MenuButton::Resume | MenuButton::CloseDialogue => {
+                next_game_state.set(GameState::Playing)
+            }
+            MenuButton::SaveGame => {
+                save_game_event_writer.send(SaveGameEvent);
+            }
+            MenuButton::LoadGame => {
+                load_game_event_writer.send(LoadGameEvent);
+            }
+            MenuButton::MainMenu => {
+                next_camera_state.set(CameraState::MainMenuCamera);
+                next_game_state.set(GameState::MainMenu);
+            }
+            MenuButton::TogglePause => match app_state.get() {
+                GameState::Playing => next_game_state.set(GameState::Pause),
+                GameState::Pause => next_game_state.set(GameState::Playing),
+                _ => {}
+            },
+            MenuButton::StepTick => {
+                if *app_state.get() == GameState::Pause {
+                    step_simulation_event_writer.send(StepSimulationEvent);
+                }
+            }
+            MenuButton::Speed(speed) => {
+                *simulation_speed = *speed;
+                // picking a speed while paused plays at it
+                if *app_state.get() == GameState::Pause {
+                    next_game_state.set(GameState::Playing);
+                }
+            }
+        }
+    }
+}
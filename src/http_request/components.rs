@@ -0,0 +1,113 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    GET,
+    POST,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub url: String,
+    pub method: HttpMethod,
+    pub headers: HashMap<String, String>,
+    pub body: serde_json::Value,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct PendingConversationHttpRequests {
+    pub requests: Vec<HttpRequest>,
+}
+
+// Base url of the conversation backend. Defaults to `API_URL`, but can be
+// pointed somewhere else (e.g. a local mock server) with `GOSSIP_API_URL`
+#[derive(Resource, Debug, Clone)]
+pub struct ConversationApiConfig {
+    pub base_url: String,
+}
+
+impl Default for ConversationApiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: std::env::var("GOSSIP_API_URL").unwrap_or_else(|_| API_URL.to_string()),
+        }
+    }
+}
+
+impl ConversationApiConfig {
+    pub fn converse_url(&self) -> String {
+        format!(
+            "{}/characters/converse",
+            self.base_url.trim_end_matches('/')
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationLine {
+    pub speaker_uuid: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationEmote {
+    pub entity_uuid: String,
+    pub emote: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationResponse {
+    pub entity_a_uuid: String,
+    pub entity_b_uuid: String,
+    #[serde(default)]
+    pub conversation: Vec<ConversationLine>,
+    #[serde(default)]
+    pub emotes: Vec<ConversationEmote>,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct ConversationHistoryResource {
+    pub entities_to_emote: VecDeque<ConversationEmote>,
+    pub conversations: Vec<ConversationResponse>,
+}
+
+impl ConversationHistoryResource {
+    pub fn record(&mut self, response: ConversationResponse) {
+        self.entities_to_emote
+            .extend(response.emotes.iter().cloned());
+        self.conversations.push(response);
+    }
+
+    pub fn latest_between(&self, uuid_a: &str, uuid_b: &str) -> Option<&ConversationResponse> {
+        self.conversations.iter().rev().find(|response| {
+            (response.entity_a_uuid == uuid_a && response.entity_b_uuid == uuid_b)
+                || (response.entity_a_uuid == uuid_b && response.entity_b_uuid == uuid_a)
+        })
+    }
+}
+
+pub type HttpResult = Result<ConversationResponse, String>;
+
+// Responses come back on ehttp's worker threads, so they are funneled
+// through a channel and drained on the main schedule
+#[derive(Resource, Debug)]
+pub struct HttpResponseChannel {
+    pub sender: Sender<HttpResult>,
+    pub receiver: Mutex<Receiver<HttpResult>>,
+    pub in_flight: usize,
+}
+
+impl Default for HttpResponseChannel {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+            in_flight: 0,
+        }
+    }
+}
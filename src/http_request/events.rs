@@ -0,0 +1,8 @@
+use crate::prelude::*;
+
+use super::components::ConversationResponse;
+
+#[derive(Event, Debug)]
+pub struct ConversationHistoryEvent {
+    pub response: ConversationResponse,
+}
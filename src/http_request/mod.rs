@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+mod systems;
+use systems::{receive_http_responses, send_pending_http_requests};
+
+pub mod components;
+use components::{
+    ConversationApiConfig, ConversationHistoryResource, HttpResponseChannel,
+    PendingConversationHttpRequests,
+};
+
+pub mod events;
+use events::ConversationHistoryEvent;
+
+pub struct HttpRequestPlugin;
+
+impl Plugin for HttpRequestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConversationApiConfig>()
+            .init_resource::<PendingConversationHttpRequests>()
+            .init_resource::<ConversationHistoryResource>()
+            .init_resource::<HttpResponseChannel>()
+            .add_event::<ConversationHistoryEvent>()
+            .add_systems(
+                Update,
+                (send_pending_http_requests, receive_http_responses).chain(),
+            );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::prelude::*;
+
+// components
+use crate::gameplay::state::components::HttpRequestState;
+use crate::http_request::components::{
+    ConversationHistoryResource, HttpMethod, HttpRequest, HttpResponseChannel, HttpResult,
+    PendingConversationHttpRequests,
+};
+
+// events
+use crate::http_request::events::ConversationHistoryEvent;
+
+fn build_ehttp_request(request: &HttpRequest) -> Result<ehttp::Request, String> {
+    let mut ehttp_request = match request.method {
+        HttpMethod::GET => ehttp::Request::get(&request.url),
+        HttpMethod::POST => {
+            ehttp::Request::json(&request.url, &request.body).map_err(|e| e.to_string())?
+        }
+    };
+
+    for (key, value) in request.headers.iter() {
+        ehttp_request.headers.insert(key, value);
+    }
+
+    Ok(ehttp_request)
+}
+
+fn parse_conversation_response(result: ehttp::Result<ehttp::Response>) -> HttpResult {
+    let response = result?;
+    if !response.ok {
+        return Err(format!(
+            "{} responded with {} {}",
+            response.url, response.status, response.status_text
+        ));
+    }
+
+    response.json().map_err(|e| e.to_string())
+}
+
+pub fn send_pending_http_requests(
+    mut pending_requests: ResMut<PendingConversationHttpRequests>,
+    mut response_channel: ResMut<HttpResponseChannel>,
+    mut http_state_next_state: ResMut<NextState<HttpRequestState>>,
+) {
+    if pending_requests.requests.is_empty() {
+        return;
+    }
+
+    for request in pending_requests.requests.drain(..) {
+        let sender = response_channel.sender.clone();
+        match build_ehttp_request(&request) {
+            Ok(ehttp_request) => {
+                ehttp::fetch(ehttp_request, move |result| {
+                    // the receiver only goes away when the app shuts down
+                    let _ = sender.send(parse_conversation_response(result));
+                });
+            }
+            Err(error) => {
+                let _ = sender.send(Err(error));
+            }
+        }
+        response_channel.in_flight += 1;
+    }
+
+    http_state_next_state.set(HttpRequestState::Loading);
+}
+
+pub fn receive_http_responses(
+    mut response_channel: ResMut<HttpResponseChannel>,
+    mut conversation_history: ResMut<ConversationHistoryResource>,
+    mut conversation_history_event_writer: EventWriter<ConversationHistoryEvent>,
+    mut http_state_next_state: ResMut<NextState<HttpRequestState>>,
+) {
+    let results: Vec<HttpResult> = match response_channel.receiver.lock() {
+        Ok(receiver) => receiver.try_iter().collect(),
+        Err(_) => return,
+    };
+
+    for result in results {
+        response_channel.in_flight = response_channel.in_flight.saturating_sub(1);
+
+        match result {
+            Ok(response) => {
+                conversation_history.record(response.clone());
+                conversation_history_event_writer.send(ConversationHistoryEvent { response });
+                if response_channel.in_flight == 0 {
+                    http_state_next_state.set(HttpRequestState::Success);
+                }
+            }
+            Err(error) => {
+                error!("Conversation request failed: {}", error);
+                http_state_next_state.set(HttpRequestState::Error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::components::ConversationApiConfig;
+    use crate::http_request::HttpRequestPlugin;
+    use serde_json::json;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::{Duration, Instant};
+
+    // Answers a single request with `status` and `body`, handing back the
+    // request line and body it was sent
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            format!(
+                "{}{}",
+                request_line.trim(),
+                String::from_utf8(request_body).unwrap()
+            )
+        });
+        (base_url, handle)
+    }
+
+    fn test_app(base_url: String) -> App {
+        let mut app = App::new();
+        app.init_state::<HttpRequestState>()
+            .insert_resource(ConversationApiConfig { base_url })
+            .add_plugins(HttpRequestPlugin);
+        let url = app.world.resource::<ConversationApiConfig>().converse_url();
+        app.world
+            .resource_mut::<PendingConversationHttpRequests>()
+            .requests
+            .push(HttpRequest {
+                url,
+                method: HttpMethod::POST,
+                headers: HashMap::new(),
+                body: json!({ "entity_a_uuid": "a", "entity_b_uuid": "b" }),
+            });
+        app
+    }
+
+    fn http_state(app: &App) -> HttpRequestState {
+        *app.world.resource::<State<HttpRequestState>>().get()
+    }
+
+    // updates until the request has been answered one way or the other
+    fn run_until_settled(app: &mut App) -> HttpRequestState {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        app.update();
+        assert!(app
+            .world
+            .resource::<PendingConversationHttpRequests>()
+            .requests
+            .is_empty());
+        while Instant::now() < deadline {
+            app.update();
+            match http_state(app) {
+                HttpRequestState::Success | HttpRequestState::Error => return http_state(app),
+                _ => thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        panic!("no response from the mock server");
+    }
+
+    #[test]
+    fn conversation_response_fills_history() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"{
+                "entity_a_uuid": "a",
+                "entity_b_uuid": "b",
+                "conversation": [{ "speaker_uuid": "a", "text": "Hi b" }],
+                "emotes": [
+                    { "entity_uuid": "a", "emote": "happiness" },
+                    { "entity_uuid": "b", "emote": "fear" }
+                ]
+            }"#,
+        );
+        let mut app = test_app(base_url);
+
+        assert_eq!(run_until_settled(&mut app), HttpRequestState::Success);
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /characters/converse HTTP/1.1"));
+        assert!(request.contains(r#""entity_a_uuid":"a""#));
+
+        let history = app.world.resource::<ConversationHistoryResource>();
+        assert_eq!(history.conversations.len(), 1);
+        assert_eq!(history.conversations[0].conversation[0].text, "Hi b");
+        let emotes: Vec<_> = history
+            .entities_to_emote
+            .iter()
+            .map(|emote| (emote.entity_uuid.as_str(), emote.emote.as_str()))
+            .collect();
+        assert_eq!(emotes, vec![("a", "happiness"), ("b", "fear")]);
+        assert!(history.latest_between("b", "a").is_some());
+    }
+
+    #[test]
+    fn error_status_is_an_error() {
+        let (base_url, server) = serve_once("500 Internal Server Error", r#"{"error":"down"}"#);
+        let mut app = test_app(base_url);
+
+        assert_eq!(run_until_settled(&mut app), HttpRequestState::Error);
+        server.join().unwrap();
+        let history = app.world.resource::<ConversationHistoryResource>();
+        assert!(history.conversations.is_empty());
+        assert!(history.entities_to_emote.is_empty());
+    }
+
+    #[test]
+    fn malformed_body_is_an_error() {
+        let (base_url, server) = serve_once("200 OK", r#"{"entity_a_uuid": "a""#);
+        let mut app = test_app(base_url);
+
+        assert_eq!(run_until_settled(&mut app), HttpRequestState::Error);
+        server.join().unwrap();
+        assert!(app
+            .world
+            .resource::<ConversationHistoryResource>()
+            .conversations
+            .is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+// bevy systems take their resources and queries as parameters, so long
+// parameter lists and query types are the norm here
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+// packages
+use crate::helpers::bindgen;
+pub mod prelude {
+    pub use bevy::prelude::TimerMode;
+    pub use bevy::prelude::*;
+    pub use bevy::sprite::TextureAtlas;
+    pub use bevy::window::PrimaryWindow;
+    #[cfg(feature = "inspector")]
+    pub use bevy_inspector_egui::prelude::*;
+    pub use rand::prelude::*;
+    pub use serde;
+    pub use serde_wasm_bindgen;
+    pub use std::collections::HashMap;
+    pub use std::path::PathBuf;
+    pub use wasm_bindgen::prelude::*;
+    pub use wasm_bindgen::JsValue;
+    pub const SCREEN_WIDTH: f32 = 1600.0;
+    pub const SCREEN_HEIGHT: f32 = 960.0;
+    pub const PLAYER_SIZE: f32 = 16.0;
+    // pub use pecs::prelude::*;
+    pub const TILE_SIZE: f32 = 16.;
+    pub const CAMERA_SCALE_FACTOR: f32 = 0.3;
+    // fixed ticks per second of game time, however fast frames are drawn
+    pub const SIMULATION_HZ: f64 = 60.0;
+    pub const API_URL: &str = "http://localhost:7070";
+    pub use crate::bindgen::*;
+    // pub use crate::CryptoCreature;
+}
+
+// modules
+pub mod data;
+pub mod gameplay;
+pub mod helpers;
+pub mod http_request;
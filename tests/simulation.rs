@@ -0,0 +1,124 @@
+// Drives the headless farm the way gossip-sim-batch does, a few game minutes
+// at a time
+use std::time::{Duration, Instant};
+
+use gossip_farm_sim::prelude::*;
+
+use gossip_farm_sim::gameplay::components::FarmTuning;
+use gossip_farm_sim::gameplay::npc::components::Cow;
+use gossip_farm_sim::gameplay::player::components::EtherPet;
+use gossip_farm_sim::gameplay::rng::components::SimRng;
+use gossip_farm_sim::gameplay::simulation::components::SimulationStats;
+use gossip_farm_sim::gameplay::simulation::report::SimulationReport;
+use gossip_farm_sim::gameplay::simulation::SimulationPlugin;
+use gossip_farm_sim::gameplay::state::components::GameState;
+use gossip_farm_sim::gameplay::tile::components::LoadedMap;
+use gossip_farm_sim::http_request::components::ConversationHistoryResource;
+
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+// a quarter second of game time per update, 15 fixed ticks
+const STEP_SECONDS: f64 = 0.25;
+const SIMULATED_SECONDS: f64 = 240.0;
+
+fn farm(seed: u64) -> App {
+    farm_with_tuning(seed, FarmTuning::default())
+}
+
+fn farm_with_tuning(seed: u64, tuning: FarmTuning) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        SimulationPlugin {
+            step: Duration::from_secs_f64(STEP_SECONDS),
+        },
+    ))
+    .insert_resource(SimRng::new(seed))
+    .insert_resource(tuning);
+    app.finish();
+    app.cleanup();
+
+    let load_started = Instant::now();
+    while *app.world.resource::<State<GameState>>().get() != GameState::Playing {
+        assert!(
+            load_started.elapsed() < LOAD_TIMEOUT,
+            "the farm didn't finish loading"
+        );
+        app.update();
+    }
+    app
+}
+
+fn run(app: &mut App) {
+    for _ in 0..(SIMULATED_SECONDS / STEP_SECONDS) as usize {
+        app.update();
+    }
+}
+
+fn report_json(app: &mut App) -> String {
+    serde_json::to_string(&SimulationReport::collect(&mut app.world, 1)).unwrap()
+}
+
+#[test]
+fn pets_spawn_and_talk() {
+    let mut app = farm(7);
+    let pets = app
+        .world
+        .query_filtered::<(), With<EtherPet>>()
+        .iter(&app.world)
+        .count();
+    assert!(pets > 1, "only {} pets spawned", pets);
+    // cows come from the map's cow_spawn objects
+    let cows = app
+        .world
+        .query_filtered::<(), With<Cow>>()
+        .iter(&app.world)
+        .count();
+    let cow_spawn_points = app
+        .world
+        .resource::<LoadedMap>()
+        .spawn_points("cow_spawn")
+        .len();
+    assert_eq!(cows, cow_spawn_points);
+    assert!(cows > 0);
+
+    run(&mut app);
+
+    assert!(!app
+        .world
+        .resource::<ConversationHistoryResource>()
+        .conversations
+        .is_empty());
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.elapsed_seconds >= (SIMULATED_SECONDS - 1.0) as f32);
+    assert!(stats.pets.values().any(|pet| pet.conversations > 0));
+}
+
+#[test]
+fn same_seed_gives_the_same_report() {
+    let mut first = farm(11);
+    let mut second = farm(11);
+    run(&mut first);
+    run(&mut second);
+    assert_eq!(report_json(&mut first), report_json(&mut second));
+}
+
+#[test]
+fn tuning_reaches_the_pets_and_the_report() {
+    let tuning = FarmTuning {
+        convo_distance: 24.0,
+        chat_cooldown_seconds: 3.0,
+        roam_max_time: 9.0,
+        idle_max_time: 1.0,
+        ..default()
+    };
+    let mut app = farm_with_tuning(7, tuning.clone());
+
+    let report = SimulationReport::collect(&mut app.world, 1);
+    assert_eq!(report.tuning, tuning);
+    assert!(!report.pets.is_empty());
+    for pet in report.pets.iter() {
+        assert_eq!(pet.chat_cooldown_seconds, 3.0);
+        assert_eq!(pet.roam_max_time, 9.0);
+        assert_eq!(pet.idle_max_time, 1.0);
+    }
+}